@@ -0,0 +1,10 @@
+use crate::formula::Formula;
+use crate::literal::Literal;
+use crate::model::Model;
+
+// A solver that can be asked about the same formula repeatedly under different
+// assumptions. The formula may grow between calls but clauses are never removed
+// so anything the backend learns remains true.
+pub trait Backend {
+    fn solve(&mut self, formula: &Formula, assumptions: &[Literal]) -> Option<Model>;
+}
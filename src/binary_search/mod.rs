@@ -0,0 +1,27 @@
+use crate::search_strategy::SearchStrategy;
+
+// Halves the range between the bounds with each query, so it needs about
+// log2(upper - lower) queries whichever way the answers go.
+pub struct BinarySearch;
+
+impl SearchStrategy for BinarySearch {
+    fn search(&self, lower: usize, upper: usize, satisfiable: &mut dyn FnMut(usize) -> bool) -> usize {
+        let mut lower = lower;
+        let mut upper = upper;
+
+        while lower < upper {
+            let middle = lower + (upper - lower).div_ceil(2);
+
+            if satisfiable(middle) {
+                lower = middle;
+            } else {
+                upper = middle - 1;
+            }
+        }
+
+        lower
+    }
+}
+
+#[cfg(test)]
+mod test;
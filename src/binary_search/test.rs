@@ -0,0 +1,19 @@
+use super::*;
+use crate::search_strategy::test;
+
+type Subject = BinarySearch;
+
+fn search(lower: usize, upper: usize, answer: usize) -> (usize, Vec<usize>) {
+    test::search(&Subject {}, lower, upper, answer)
+}
+
+mod search {
+    use super::*;
+
+    #[test]
+    fn it_halves_the_range_with_each_query() {
+        assert_eq!(search(20, 25, 25), (25, vec![23, 24, 25]));
+        assert_eq!(search(20, 25, 21), (21, vec![23, 21, 22]));
+        assert_eq!(search(20, 25, 20), (20, vec![23, 21]));
+    }
+}
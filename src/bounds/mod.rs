@@ -2,6 +2,7 @@ use std::ops::RangeInclusive;
 use std::cmp::min;
 
 use crate::utility::Utility;
+use crate::bounds_error::BoundsError;
 
 pub struct Bounds {
     n: usize,
@@ -11,11 +12,74 @@ pub struct Bounds {
 
 impl Bounds {
     pub fn new(n: usize, length_of_string: usize, max_permutations: &[usize]) -> Self {
+        match Self::try_new(n, length_of_string, max_permutations) {
+            Ok(bounds) => bounds,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    pub fn try_new(n: usize, length_of_string: usize, max_permutations: &[usize]) -> Result<Self, BoundsError> {
+        Self::validate(n, max_permutations)?;
+
         let max_permutations = max_permutations.to_vec();
 
-        Self { n, length_of_string, max_permutations }
+        Ok(Self { n, length_of_string, max_permutations })
     }
 
+    // Wasting another symbol can't lose permutations or gain more than n, so
+    // the table can't decrease, jump or go past n!. It stays at n! for longer strings than the
+    // shortest, whose extra symbols are all wasted.
+    fn validate(n: usize, max_permutations: &[usize]) -> Result<(), BoundsError> {
+        let all_permutations = Utility::factorial(n);
+        let table = || max_permutations.to_vec();
+
+        for (index, pair) in max_permutations.windows(2).enumerate() {
+            if pair[0] > pair[1] {
+                return Err(BoundsError::Decreasing { index, max_permutations: table() });
+            }
+        }
+
+        if max_permutations.iter().any(|p| *p > all_permutations) {
+            return Err(BoundsError::ExceedsAllPermutations { all_permutations, max_permutations: table() });
+        }
+
+        // Each wasted symbol can be followed by at most n new permutations.
+        if max_permutations.first().is_some_and(|p| *p > n) {
+            return Err(BoundsError::FirstExceedsSymbols { n, max_permutations: table() });
+        }
+
+        for (index, pair) in max_permutations.windows(2).enumerate() {
+            if pair[1] - pair[0] > n {
+                return Err(BoundsError::IncreasesByMoreThanSymbols { index, n, max_permutations: table() });
+            }
+        }
+
+        Ok(())
+    }
+
+    // The formula is trivially unsatisfiable if the string is too short or
+    // any index has no number of wasted symbols that can still reach the goal.
+    pub fn diagnose(&self) -> Result<(), BoundsError> {
+        if self.number_of_permutations(self.goal_index(), self.goal_wasted_symbols()).is_none() {
+            let length_of_string = self.length_of_string;
+            let wasted_symbols = self.n - 1 + self.goal_wasted_symbols();
+
+            return Err(BoundsError::StringTooShort { length_of_string, wasted_symbols });
+        }
+
+        for index in (self.n - 1)..=self.goal_index() {
+            if self.waste_range(index).is_none() {
+                let goal_permutations = self.goal_permutations();
+                return Err(BoundsError::EmptyWasteRange { index, goal_permutations });
+            }
+        }
+
+        Ok(())
+    }
+
+    // The permutations completed by each index aren't bounded separately. Every
+    // symbol after the first n - 1 either completes a new permutation or is
+    // wasted, so a bound on one is the same bound on the other.
     pub fn wasted_symbol_ranges(&self) -> Vec<RangeInclusive<usize>> {
         let indexes = (self.n - 1)..=self.goal_index();
 
@@ -23,6 +87,10 @@ impl Bounds {
     }
 
     fn allowed_waste(&self, index: usize) -> RangeInclusive<usize> {
+        self.waste_range(index).unwrap_or(0..=0)
+    }
+
+    fn waste_range(&self, index: usize) -> Option<RangeInclusive<usize>> {
         let mut wasted_symbols = vec![];
 
         // println!("trying to reach goal of {} permutations", self.goal_permutations());
@@ -68,10 +136,10 @@ impl Bounds {
         }
 
         if wasted_symbols.is_empty() {
-            return 0..=0;
+            return None;
         }
 
-        *wasted_symbols.first().unwrap()..=*wasted_symbols.last().unwrap()
+        Some(*wasted_symbols.first().unwrap()..=*wasted_symbols.last().unwrap())
     }
 
     fn max_perms_at_last_waste(&self, wasted_symbols: usize, current_permutations: usize) -> usize {
@@ -1,4 +1,5 @@
 use super::*;
+use crate::bounds_error::BoundsError;
 
 type Subject = Bounds;
 
@@ -16,6 +17,75 @@ mod new {
         assert_eq!(subject.length_of_string, LENGTH);
         assert_eq!(subject.max_permutations, &[3, 5]);
     }
+
+    #[test]
+    fn it_returns_an_error_if_the_max_permutations_are_inconsistent() {
+        let error = Subject::try_new(N, LENGTH, &[5, 3]).err().unwrap();
+        let expected = BoundsError::Decreasing { index: 0, max_permutations: vec![5, 3] };
+
+        assert_eq!(error, expected);
+        assert!(Subject::try_new(N, LENGTH, &[3, 5]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "The max permutations decrease after index 0: [5, 3]")]
+    fn it_panics_if_the_max_permutations_decrease() {
+        Subject::new(N, LENGTH, &[5, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The max permutations exceed 6: [3, 7]")]
+    fn it_panics_if_the_max_permutations_exceed_n_factorial() {
+        Subject::new(N, LENGTH, &[3, 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The first max permutations exceed 3: [6]")]
+    fn it_panics_if_the_first_max_permutations_exceed_n() {
+        Subject::new(N, LENGTH, &[6]);
+    }
+
+    #[test]
+    fn it_returns_an_error_if_the_max_permutations_increase_by_more_than_n() {
+        let error = Subject::try_new(N, LENGTH, &[2, 6]).err().unwrap();
+        let expected = BoundsError::IncreasesByMoreThanSymbols { index: 0, n: N, max_permutations: vec![2, 6] };
+
+        assert_eq!(error, expected);
+        assert!(Subject::try_new(N, LENGTH, &[2, 5]).is_ok());
+    }
+
+    #[test]
+    fn it_accepts_max_permutations_that_stay_at_n_factorial() {
+        assert!(Subject::try_new(N, 11, &[3, 6, 6]).is_ok());
+    }
+}
+
+mod diagnose {
+    use super::*;
+
+    #[test]
+    fn it_returns_ok_if_every_index_can_reach_the_goal() {
+        let subject = Subject::new(N, LENGTH, &[3, 5]);
+
+        assert_eq!(subject.diagnose(), Ok(()));
+    }
+
+    #[test]
+    fn it_returns_an_error_if_the_string_is_too_short_for_the_wasted_symbols() {
+        let subject = Subject::new(N, 3, &[3, 5]);
+        let expected = BoundsError::StringTooShort { length_of_string: 3, wasted_symbols: 4 };
+
+        assert_eq!(subject.diagnose(), Err(expected));
+    }
+
+    #[test]
+    fn it_returns_an_error_for_the_first_index_that_cant_reach_the_goal() {
+        // Wasting one symbol can't produce 9 permutations for 4 symbols.
+        let subject = Subject::new(4, 13, &[4]);
+        let expected = BoundsError::EmptyWasteRange { index: 7, goal_permutations: 9 };
+
+        assert_eq!(subject.diagnose(), Err(expected));
+    }
 }
 
 mod allowed_waste {
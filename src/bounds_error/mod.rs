@@ -0,0 +1,38 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum BoundsError {
+    Decreasing { index: usize, max_permutations: Vec<usize> },
+    ExceedsAllPermutations { all_permutations: usize, max_permutations: Vec<usize> },
+    FirstExceedsSymbols { n: usize, max_permutations: Vec<usize> },
+    IncreasesByMoreThanSymbols { index: usize, n: usize, max_permutations: Vec<usize> },
+    StringTooShort { length_of_string: usize, wasted_symbols: usize },
+    EmptyWasteRange { index: usize, goal_permutations: usize },
+}
+
+impl Display for BoundsError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Decreasing { index, max_permutations } =>
+                write!(f, "The max permutations decrease after index {}: {:?}", index, max_permutations),
+
+            Self::ExceedsAllPermutations { all_permutations, max_permutations } =>
+                write!(f, "The max permutations exceed {}: {:?}", all_permutations, max_permutations),
+
+            Self::FirstExceedsSymbols { n, max_permutations } =>
+                write!(f, "The first max permutations exceed {}: {:?}", n, max_permutations),
+
+            Self::IncreasesByMoreThanSymbols { index, n, max_permutations } =>
+                write!(f, "The max permutations increase by more than {} after index {}: {:?}", n, index, max_permutations),
+
+            Self::StringTooShort { length_of_string, wasted_symbols } =>
+                write!(f, "A string of length {} can't waste {} symbols", length_of_string, wasted_symbols),
+
+            Self::EmptyWasteRange { index, goal_permutations } =>
+                write!(f, "No number of wasted symbols at index {} can still reach {} permutations", index, goal_permutations),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
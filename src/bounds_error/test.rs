@@ -0,0 +1,31 @@
+use super::*;
+
+type Subject = BoundsError;
+
+mod display {
+    use super::*;
+
+    #[test]
+    fn it_explains_what_is_wrong_with_the_table() {
+        let subject = Subject::Decreasing { index: 1, max_permutations: vec![3, 5, 4] };
+        assert_eq!(subject.to_string(), "The max permutations decrease after index 1: [3, 5, 4]");
+
+        let subject = Subject::ExceedsAllPermutations { all_permutations: 6, max_permutations: vec![3, 7] };
+        assert_eq!(subject.to_string(), "The max permutations exceed 6: [3, 7]");
+
+        let subject = Subject::FirstExceedsSymbols { n: 3, max_permutations: vec![6] };
+        assert_eq!(subject.to_string(), "The first max permutations exceed 3: [6]");
+
+        let subject = Subject::IncreasesByMoreThanSymbols { index: 0, n: 3, max_permutations: vec![2, 6] };
+        assert_eq!(subject.to_string(), "The max permutations increase by more than 3 after index 0: [2, 6]");
+    }
+
+    #[test]
+    fn it_explains_why_the_goal_cant_be_reached() {
+        let subject = Subject::StringTooShort { length_of_string: 3, wasted_symbols: 4 };
+        assert_eq!(subject.to_string(), "A string of length 3 can't waste 4 symbols");
+
+        let subject = Subject::EmptyWasteRange { index: 5, goal_permutations: 6 };
+        assert_eq!(subject.to_string(), "No number of wasted symbols at index 5 can still reach 6 permutations");
+    }
+}
@@ -0,0 +1,547 @@
+use std::collections::{BinaryHeap, HashSet};
+use std::io::{Write, BufWriter};
+
+use crate::formula::Formula;
+use crate::clause::Clause;
+use crate::variable::Variable;
+use crate::literal::Literal;
+use crate::model::Model;
+use crate::backend::Backend;
+use crate::compression::Compression;
+
+const FALSE: u8 = 0;
+const TRUE: u8 = 1;
+const UNASSIGNED: u8 = 2;
+
+const RESTART_INTERVAL: usize = 100;
+const ACTIVITY_DECAY: f64 = 0.95;
+const ACTIVITY_LIMIT: f64 = 1e100;
+
+// A conflict-driven clause learning solver that runs in-process. Literals are
+// coded as 2 * index + sign where index is the variable's number minus one and
+// sign is 1 for negative literals, so negating a literal flips the lowest bit.
+pub struct Cdcl {
+    clauses: Vec<Vec<usize>>,
+    learnt: Vec<bool>,
+    deleted: Vec<bool>,
+    lbd: Vec<usize>,
+    watches: Vec<Vec<usize>>,
+    values: Vec<u8>,
+    levels: Vec<usize>,
+    reasons: Vec<Option<usize>>,
+    phases: Vec<bool>,
+    activity: Vec<f64>,
+    increment: f64,
+    heap: BinaryHeap<(u64, usize)>,
+    trail: Vec<usize>,
+    trail_limits: Vec<usize>,
+    propagated: usize,
+    seen: Vec<bool>,
+    added: HashSet<Clause>,
+    max_learnts: usize,
+    inconsistent: bool,
+    proof: Option<BufWriter<Box<dyn Write>>>,
+}
+
+impl Cdcl {
+    pub fn new() -> Self {
+        Self {
+            clauses: vec![],
+            learnt: vec![],
+            deleted: vec![],
+            lbd: vec![],
+            watches: vec![],
+            values: vec![],
+            levels: vec![],
+            reasons: vec![],
+            phases: vec![],
+            activity: vec![],
+            increment: 1.0,
+            heap: BinaryHeap::new(),
+            trail: vec![],
+            trail_limits: vec![],
+            propagated: 0,
+            seen: vec![],
+            added: HashSet::new(),
+            max_learnts: 1000,
+            inconsistent: false,
+            proof: None,
+        }
+    }
+
+    // Writes a DRAT proof of the clauses it learns and deletes so that an
+    // unsatisfiable answer without assumptions can be checked independently.
+    pub fn with_proof(filename: &String) -> Self {
+        let file = Compression::create(filename);
+        let proof = Some(BufWriter::new(file));
+
+        Self { proof, ..Self::new() }
+    }
+
+    fn trace(&mut self, deletion: bool, codes: &[usize]) {
+        let proof = match &mut self.proof {
+            Some(proof) => proof,
+            None => return,
+        };
+
+        if deletion {
+            write!(proof, "d ").unwrap();
+        }
+
+        for code in codes {
+            let number = (code >> 1) as isize + 1;
+            write!(proof, "{} ", if code & 1 == 0 { number } else { -number }).unwrap();
+        }
+
+        writeln!(proof, "0").unwrap();
+    }
+
+    fn code(literal: &Literal) -> usize {
+        let index = literal.variable.number - 1;
+
+        2 * index + if literal.positive { 0 } else { 1 }
+    }
+
+    fn value(values: &[u8], code: usize) -> u8 {
+        match values[code >> 1] {
+            UNASSIGNED => UNASSIGNED,
+            value => value ^ (code & 1) as u8,
+        }
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_limits.len()
+    }
+
+    fn reserve(&mut self, number_of_variables: usize) {
+        for index in self.values.len()..number_of_variables {
+            self.watches.push(vec![]);
+            self.watches.push(vec![]);
+            self.values.push(UNASSIGNED);
+            self.levels.push(0);
+            self.reasons.push(None);
+            self.phases.push(false);
+            self.activity.push(0.0);
+            self.seen.push(false);
+            self.heap.push((0, index));
+        }
+    }
+
+    // Clauses are only added at decision level zero so anything already
+    // assigned is permanent and can be simplified away.
+    fn add_clause(&mut self, clause: &Clause) {
+        let codes = clause.literals().iter().map(Self::code).collect::<Vec<_>>();
+
+        if codes.iter().any(|c| codes.contains(&(c ^ 1))) {
+            return;
+        }
+
+        if codes.iter().any(|c| Self::value(&self.values, *c) == TRUE) {
+            return;
+        }
+
+        let codes = codes.into_iter()
+            .filter(|c| Self::value(&self.values, *c) == UNASSIGNED)
+            .collect::<Vec<_>>();
+
+        match codes.len() {
+            0 => self.inconsistent = true,
+            1 => self.assign(codes[0], None),
+            _ => { self.attach(codes, false, 0); },
+        }
+    }
+
+    fn attach(&mut self, codes: Vec<usize>, learnt: bool, lbd: usize) -> usize {
+        let index = self.clauses.len();
+
+        self.watches[codes[0]].push(index);
+        self.watches[codes[1]].push(index);
+
+        self.clauses.push(codes);
+        self.learnt.push(learnt);
+        self.deleted.push(false);
+        self.lbd.push(lbd);
+
+        index
+    }
+
+    fn assign(&mut self, code: usize, reason: Option<usize>) {
+        let index = code >> 1;
+
+        self.values[index] = TRUE ^ (code & 1) as u8;
+        self.levels[index] = self.decision_level();
+        self.reasons[index] = reason;
+        self.trail.push(code);
+    }
+
+    // Each clause watches its first two literals. A clause only needs to be
+    // visited when one of them becomes false, at which point it either finds
+    // another literal to watch, becomes unit or is in conflict.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let false_code = self.trail[self.propagated] ^ 1;
+            self.propagated += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_code]);
+            let mut conflict = None;
+            let mut i = 0;
+
+            while i < watchers.len() {
+                let index = watchers[i];
+
+                if self.deleted[index] {
+                    watchers.swap_remove(i);
+                    continue;
+                }
+
+                let clause = &mut self.clauses[index];
+
+                if clause[0] == false_code {
+                    clause.swap(0, 1);
+                }
+
+                let first = clause[0];
+
+                if Self::value(&self.values, first) == TRUE {
+                    i += 1;
+                    continue;
+                }
+
+                let values = &self.values;
+                let replacement = (2..clause.len())
+                    .find(|k| Self::value(values, clause[*k]) != FALSE);
+
+                if let Some(k) = replacement {
+                    clause.swap(1, k);
+                    self.watches[clause[1]].push(index);
+                    watchers.swap_remove(i);
+                    continue;
+                }
+
+                if Self::value(&self.values, first) == FALSE {
+                    conflict = Some(index);
+                    break;
+                }
+
+                self.assign(first, Some(index));
+                i += 1;
+            }
+
+            self.watches[false_code] = watchers;
+
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+
+        None
+    }
+
+    // Walks back along the trail from the conflict until a single literal of
+    // the current decision level remains (the first unique implication point).
+    fn analyze(&mut self, conflict: usize) -> (Vec<usize>, usize, usize) {
+        let mut learnt = vec![0];
+        let mut unresolved = 0;
+        let mut index = self.trail.len();
+        let mut reason = conflict;
+        let mut pivot = None;
+
+        loop {
+            let start = if pivot.is_none() { 0 } else { 1 };
+
+            for k in start..self.clauses[reason].len() {
+                let code = self.clauses[reason][k];
+                let variable = code >> 1;
+
+                if self.seen[variable] || self.levels[variable] == 0 {
+                    continue;
+                }
+
+                self.seen[variable] = true;
+                self.bump(variable);
+
+                if self.levels[variable] >= self.decision_level() {
+                    unresolved += 1;
+                } else {
+                    learnt.push(code);
+                }
+            }
+
+            loop {
+                index -= 1;
+
+                if self.seen[self.trail[index] >> 1] {
+                    break;
+                }
+            }
+
+            let code = self.trail[index];
+            self.seen[code >> 1] = false;
+            pivot = Some(code);
+            unresolved -= 1;
+
+            if unresolved == 0 {
+                break;
+            }
+
+            reason = self.reasons[code >> 1].unwrap();
+        }
+
+        learnt[0] = pivot.unwrap() ^ 1;
+
+        for code in &learnt[1..] {
+            self.seen[code >> 1] = false;
+        }
+
+        // The literal with the highest level is watched so the clause becomes
+        // unit as soon as the solver backtracks to that level.
+        let mut backtrack_level = 0;
+
+        if learnt.len() > 1 {
+            let highest = (1..learnt.len()).max_by_key(|k| self.levels[learnt[*k] >> 1]).unwrap();
+
+            learnt.swap(1, highest);
+            backtrack_level = self.levels[learnt[1] >> 1];
+        }
+
+        let lbd = learnt.iter().map(|c| self.levels[c >> 1]).collect::<HashSet<_>>().len();
+
+        (learnt, backtrack_level, lbd)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+
+        let start = self.trail_limits[level];
+
+        for k in (start..self.trail.len()).rev() {
+            let variable = self.trail[k] >> 1;
+
+            self.phases[variable] = self.values[variable] == TRUE;
+            self.values[variable] = UNASSIGNED;
+            self.reasons[variable] = None;
+            self.heap.push((self.activity[variable].to_bits(), variable));
+        }
+
+        self.trail.truncate(start);
+        self.trail_limits.truncate(level);
+        self.propagated = start;
+    }
+
+    // Activity is always positive so its bits order the same way as the float.
+    // Entries go stale when a variable is bumped or assigned and are skipped.
+    fn bump(&mut self, variable: usize) {
+        self.activity[variable] += self.increment;
+
+        if self.activity[variable] > ACTIVITY_LIMIT {
+            for activity in &mut self.activity {
+                *activity /= ACTIVITY_LIMIT;
+            }
+
+            self.increment /= ACTIVITY_LIMIT;
+            self.rebuild_heap();
+        } else {
+            self.heap.push((self.activity[variable].to_bits(), variable));
+        }
+    }
+
+    fn rebuild_heap(&mut self) {
+        self.heap = (0..self.values.len())
+            .filter(|v| self.values[*v] == UNASSIGNED)
+            .map(|v| (self.activity[v].to_bits(), v))
+            .collect();
+    }
+
+    fn pick(&mut self) -> Option<usize> {
+        if self.heap.len() > 4 * self.values.len() + 1000 {
+            self.rebuild_heap();
+        }
+
+        while let Some((bits, variable)) = self.heap.pop() {
+            if self.values[variable] != UNASSIGNED || self.activity[variable].to_bits() != bits {
+                continue;
+            }
+
+            return Some(2 * variable + if self.phases[variable] { 0 } else { 1 });
+        }
+
+        None
+    }
+
+    // Keeps learnt clauses whose literals span few decision levels and drops
+    // half of the rest, except those that are the reason for an assignment.
+    fn reduce(&mut self) {
+        let mut candidates = (0..self.clauses.len())
+            .filter(|i| self.learnt[*i] && !self.deleted[*i] && self.lbd[*i] > 2)
+            .filter(|i| {
+                let first = self.clauses[*i][0];
+                self.reasons[first >> 1] != Some(*i) || Self::value(&self.values, first) != TRUE
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_by_key(|i| std::cmp::Reverse(self.lbd[*i]));
+
+        for index in candidates.iter().take(candidates.len() / 2) {
+            let codes = std::mem::take(&mut self.clauses[*index]);
+
+            self.trace(true, &codes);
+            self.deleted[*index] = true;
+        }
+
+        self.max_learnts += self.max_learnts / 10;
+    }
+
+    fn number_of_learnts(&self) -> usize {
+        (0..self.clauses.len()).filter(|i| self.learnt[*i] && !self.deleted[*i]).count()
+    }
+
+    // Returns None if the conflict limit is reached so the caller can restart.
+    fn search(&mut self, conflict_limit: usize, assumptions: &[usize]) -> Option<bool> {
+        let mut conflicts = 0;
+        let mut learnts = self.number_of_learnts();
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                conflicts += 1;
+
+                if self.decision_level() == 0 {
+                    self.inconsistent = true;
+                    self.trace(false, &[]);
+                    return Some(false);
+                }
+
+                let (learnt, level, lbd) = self.analyze(conflict);
+                self.trace(false, &learnt);
+                self.backtrack(level);
+
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let code = learnt[0];
+                    let index = self.attach(learnt, true, lbd);
+
+                    self.assign(code, Some(index));
+                    learnts += 1;
+                }
+
+                self.increment /= ACTIVITY_DECAY;
+                continue;
+            }
+
+            if conflicts >= conflict_limit {
+                return None;
+            }
+
+            if learnts > self.max_learnts {
+                self.reduce();
+                learnts = self.number_of_learnts();
+            }
+
+            // Assumptions are decided first, each at its own level.
+            let mut decision = None;
+
+            while self.decision_level() < assumptions.len() {
+                let code = assumptions[self.decision_level()];
+
+                match Self::value(&self.values, code) {
+                    TRUE => self.trail_limits.push(self.trail.len()),
+                    FALSE => return Some(false),
+                    _ => { decision = Some(code); break; },
+                }
+            }
+
+            let code = match decision.or_else(|| self.pick()) {
+                Some(code) => code,
+                None => return Some(true),
+            };
+
+            self.trail_limits.push(self.trail.len());
+            self.assign(code, None);
+        }
+    }
+
+    fn luby(index: usize) -> usize {
+        let mut size = 1;
+        let mut sequence = 0;
+
+        while size < index + 1 {
+            sequence += 1;
+            size = 2 * size + 1;
+        }
+
+        let mut index = index;
+
+        while size - 1 != index {
+            size = (size - 1) / 2;
+            sequence -= 1;
+            index %= size;
+        }
+
+        1 << sequence
+    }
+
+    fn flush(&mut self) {
+        if let Some(proof) = &mut self.proof {
+            proof.flush().unwrap();
+        }
+    }
+
+    fn model(&self) -> Model {
+        let literals = self.values.iter().enumerate()
+            .map(|(index, value)| Literal::new(Variable::new(index + 1), *value == TRUE))
+            .collect::<Vec<_>>();
+
+        Model::new(&literals)
+    }
+}
+
+impl Backend for Cdcl {
+    fn solve(&mut self, formula: &Formula, assumptions: &[Literal]) -> Option<Model> {
+        self.backtrack(0);
+
+        let largest = assumptions.iter().map(|l| l.variable.number).max().unwrap_or(0);
+        self.reserve(formula.number_of_variables().max(largest));
+
+        for clause in formula.clauses() {
+            if self.inconsistent {
+                break;
+            }
+
+            if self.added.insert(clause.clone()) {
+                self.add_clause(clause);
+            }
+        }
+
+        if self.inconsistent || self.propagate().is_some() {
+            self.inconsistent = true;
+            self.trace(false, &[]);
+            self.flush();
+
+            return None;
+        }
+
+        let assumptions = assumptions.iter().map(Self::code).collect::<Vec<_>>();
+
+        for restart in 0.. {
+            let result = self.search(Self::luby(restart) * RESTART_INTERVAL, &assumptions);
+
+            let model = match result {
+                Some(true) => Some(self.model()),
+                Some(false) => None,
+                None => { self.backtrack(0); continue; },
+            };
+
+            self.backtrack(0);
+            self.flush();
+
+            return model;
+        }
+
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod test;
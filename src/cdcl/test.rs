@@ -0,0 +1,153 @@
+use super::*;
+use crate::test_formulas::{formula, satisfies, pigeonhole, Random};
+
+type Subject = Cdcl;
+
+mod solve {
+    use super::*;
+
+    #[test]
+    fn it_returns_a_model_that_satisfies_the_formula() {
+        let clauses = vec![vec![1, 2], vec![-1, 3], vec![-3, -2], vec![-2, 4]];
+        let formula = formula(4, &clauses);
+
+        let model = Subject::new().solve(&formula, &[]).unwrap();
+
+        assert!(satisfies(&model, &clauses));
+    }
+
+    #[test]
+    fn it_returns_none_if_the_formula_is_unsatisfiable() {
+        let formula = formula(2, &[vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]]);
+
+        assert_eq!(Subject::new().solve(&formula, &[]), None);
+    }
+
+    #[test]
+    fn it_returns_none_if_the_formula_contains_the_empty_clause() {
+        let formula = formula(1, &[vec![1], vec![]]);
+
+        assert_eq!(Subject::new().solve(&formula, &[]), None);
+    }
+
+    #[test]
+    fn it_learns_its_way_through_the_pigeonhole_principle() {
+        let unsatisfiable = pigeonhole(6, 5);
+        let formula = formula(30, &unsatisfiable);
+
+        assert_eq!(Subject::new().solve(&formula, &[]), None);
+
+        let satisfiable = pigeonhole(5, 5);
+        let formula = self::formula(25, &satisfiable);
+        let model = Subject::new().solve(&formula, &[]).unwrap();
+
+        assert!(satisfies(&model, &satisfiable));
+    }
+
+    #[test]
+    fn it_agrees_with_brute_force_on_random_formulas() {
+        let mut random = Random::new(12345);
+
+        for _ in 0..200 {
+            let clauses = (0..40).map(|_| random.clause(3, 10)).collect::<Vec<_>>();
+
+            let expected = (0..1 << 10).any(|bits: usize| {
+                clauses.iter().all(|c| c.iter().any(|n| (bits >> (n.abs() - 1) & 1 == 1) == (*n > 0)))
+            });
+
+            let formula = formula(10, &clauses);
+            let model = Subject::new().solve(&formula, &[]);
+
+            assert_eq!(model.is_some(), expected);
+
+            if let Some(model) = model {
+                assert!(satisfies(&model, &clauses));
+            }
+        }
+    }
+
+    #[test]
+    fn it_solves_under_assumptions_without_keeping_them() {
+        let formula = formula(3, &[vec![-1, 2], vec![-2, 3]]);
+        let mut subject = Subject::new();
+
+        let model = subject.solve(&formula, &[Literal::from_dimacs(1)]).unwrap();
+        assert!(model.satisfies(&[Literal::from_dimacs(3)]));
+
+        let model = subject.solve(&formula, &[Literal::from_dimacs(1), Literal::from_dimacs(-3)]);
+        assert_eq!(model, None);
+
+        let model = subject.solve(&formula, &[Literal::from_dimacs(-3)]).unwrap();
+        assert!(model.satisfies(&[Literal::from_dimacs(-1), Literal::from_dimacs(-2)]));
+    }
+
+    #[test]
+    fn it_picks_up_clauses_and_variables_added_between_calls() {
+        let mut formula = formula(2, &[vec![1, 2]]);
+        let mut subject = Subject::new();
+
+        assert!(subject.solve(&formula, &[]).is_some());
+
+        let c = formula.new_variable();
+        let mut clause = Clause::new();
+        clause.add(Literal::negative(c));
+        formula.add_clause(clause);
+
+        for numbers in &[[-1, 3], [-2, 3]] {
+            let mut clause = Clause::new();
+            clause.add(Literal::from_dimacs(numbers[0]));
+            clause.add(Literal::from_dimacs(numbers[1]));
+            formula.add_clause(clause);
+        }
+
+        assert_eq!(subject.solve(&formula, &[]), None);
+    }
+}
+
+mod with_proof {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+
+    fn proof(name: &str, clauses: &[Vec<isize>], number_of_variables: usize) -> (bool, Vec<String>) {
+        let filename = temp_dir().join(format!("supersat-cdcl-{}.drat", name));
+        let filename = filename.to_str().unwrap().to_string();
+
+        let formula = formula(number_of_variables, clauses);
+        let satisfiable = Subject::with_proof(&filename).solve(&formula, &[]).is_some();
+
+        let lines = read_to_string(&filename).unwrap().lines().map(String::from).collect();
+        remove_file(&filename).unwrap();
+
+        (satisfiable, lines)
+    }
+
+    #[test]
+    fn it_writes_the_learnt_clauses_and_ends_with_the_empty_clause() {
+        let (satisfiable, lines) = proof("pigeonhole", &pigeonhole(5, 4), 20);
+
+        assert!(!satisfiable);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.ends_with(" 0") || line == "0"));
+        assert_eq!(lines.last().unwrap(), "0");
+    }
+
+    #[test]
+    fn it_does_not_derive_the_empty_clause_for_a_satisfiable_formula() {
+        let (satisfiable, lines) = proof("satisfiable", &[vec![1, 2], vec![-1, 2], vec![1, -2]], 2);
+
+        assert!(satisfiable);
+        assert!(!lines.contains(&"0".to_string()));
+    }
+}
+
+mod luby {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_luby_sequence() {
+        let sequence = (0..15).map(Subject::luby).collect::<Vec<_>>();
+
+        assert_eq!(sequence, vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+}
@@ -0,0 +1,113 @@
+use itertools::Itertools;
+
+use crate::utility::Utility;
+
+// A depth-first search in the style of Chaffin's algorithm that answers the
+// same question as the SAT formula: is there a string that wastes no more than
+// max_permutations.len() symbols and contains the given number of permutations?
+// The rows of max_permutations that are already known bound how many more
+// permutations the rest of the string could add, which prunes most branches.
+pub struct Chaffin {
+    n: usize,
+    max_permutations: Vec<usize>,
+    ids: Vec<Option<usize>>,
+    string: Vec<usize>,
+    seen: Vec<bool>,
+}
+
+impl Chaffin {
+    pub fn new(n: usize, max_permutations: &[usize]) -> Self {
+        let max_permutations = max_permutations.to_vec();
+        let mut ids = vec![None; (n + 1).pow(n as u32)];
+
+        for (id, permutation) in (1..=n).permutations(n).enumerate() {
+            ids[Self::window_number(n, &permutation)] = Some(id);
+        }
+
+        let string = (1..=n).collect();
+        let seen = vec![false; Utility::factorial(n)];
+
+        Self { n, max_permutations, ids, string, seen }
+    }
+
+    pub fn search(&mut self, permutations: usize) -> Option<Vec<usize>> {
+        let first = self.window_id().unwrap();
+
+        self.seen[first] = true;
+        let found = self.extend(1, 0, permutations);
+        self.seen[first] = false;
+
+        if found {
+            let string = self.string.clone();
+            self.string.truncate(self.n);
+
+            Some(string)
+        } else {
+            None
+        }
+    }
+
+    fn extend(&mut self, permutations: usize, wasted_symbols: usize, target: usize) -> bool {
+        if permutations >= target {
+            return true;
+        }
+
+        let remaining = self.max_permutations.len() - wasted_symbols;
+
+        // If the string ends in a permutation, the rest of the string starts
+        // with it so it can only add max_permutations[remaining] - 1 more.
+        if let Some(&additional) = self.max_permutations.get(remaining) {
+            let ends_in_permutation = self.window_id().is_some();
+            let additional = additional - ends_in_permutation as usize;
+
+            if permutations + additional < target {
+                return false;
+            }
+        }
+
+        // Try the symbol that continues the current cycle first since it's
+        // the only one that can avoid wasting a symbol.
+        let next = self.string[self.string.len() - self.n];
+        let symbols = (1..=self.n).filter(|s| *s != next);
+
+        for symbol in Some(next).into_iter().chain(symbols) {
+            self.string.push(symbol);
+
+            let found = match self.window_id() {
+                Some(id) if !self.seen[id] => {
+                    self.seen[id] = true;
+                    let found = self.extend(permutations + 1, wasted_symbols, target);
+                    self.seen[id] = false;
+                    found
+                },
+                Some(_) => false,
+                None if wasted_symbols < self.max_permutations.len() => {
+                    self.extend(permutations, wasted_symbols + 1, target)
+                },
+                None => false,
+            };
+
+            if found {
+                return true;
+            }
+
+            self.string.pop();
+        }
+
+        false
+    }
+
+    fn window_id(&self) -> Option<usize> {
+        let window = &self.string[(self.string.len() - self.n)..];
+
+        self.ids[Self::window_number(self.n, window)]
+    }
+
+    // Windows are numbered by reading their symbols in base n + 1.
+    fn window_number(n: usize, window: &[usize]) -> usize {
+        window.iter().fold(0, |number, s| number * (n + 1) + s)
+    }
+}
+
+#[cfg(test)]
+mod test;
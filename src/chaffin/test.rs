@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use super::*;
+use crate::oracle::Oracle;
+
+type Subject = Chaffin;
+
+fn permutations(string: &[usize], n: usize) -> usize {
+    string.windows(n)
+        .filter(|window| window.iter().collect::<HashSet<_>>().len() == n)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+mod new {
+    use super::*;
+
+    #[test]
+    fn it_numbers_each_permutation_window() {
+        let subject = Subject::new(3, &[]);
+
+        assert_eq!(subject.ids.iter().filter(|id| id.is_some()).count(), 6);
+        assert_eq!(subject.ids[Subject::window_number(3, &[1, 2, 3])], Some(0));
+        assert_eq!(subject.ids[Subject::window_number(3, &[1, 2, 1])], None);
+    }
+}
+
+mod search {
+    use super::*;
+
+    #[test]
+    fn it_returns_a_string_that_contains_the_permutations() {
+        let mut subject = Subject::new(4, &[4, 8, 12]);
+        let string = subject.search(14).unwrap();
+
+        assert_eq!(&string[..4], &[1, 2, 3, 4]);
+        assert!(permutations(&string, 4) >= 14);
+        assert!(string.len() - 3 - permutations(&string, 4) <= 3);
+    }
+
+    #[test]
+    fn it_returns_none_if_the_permutations_cant_be_reached() {
+        let mut subject = Subject::new(4, &[4, 8, 12]);
+
+        assert_eq!(subject.search(15), None);
+    }
+
+    #[test]
+    fn it_can_be_searched_more_than_once() {
+        let mut subject = Subject::new(3, &[3]);
+
+        assert_eq!(subject.search(6), Some(vec![1, 2, 3, 1, 2, 1, 3, 2, 1]));
+        assert_eq!(subject.search(6), Some(vec![1, 2, 3, 1, 2, 1, 3, 2, 1]));
+    }
+
+    #[test]
+    fn it_agrees_with_the_oracle() {
+        for n in 3..=5 {
+            let expected = Oracle::new(n).max_permutations(4);
+
+            for (row, &permutations) in expected.iter().enumerate() {
+                let mut subject = Subject::new(n, &expected[..row]);
+
+                assert!(subject.search(permutations).is_some());
+                assert!(subject.search(permutations + 1).is_none());
+            }
+        }
+    }
+}
@@ -16,6 +16,10 @@ impl Clause {
     pub fn add(&mut self, literal: Literal) {
         self.literals.insert(literal);
     }
+
+    pub fn literals(&self) -> &BTreeSet<Literal> {
+        &self.literals
+    }
 }
 
 impl Display for Clause {
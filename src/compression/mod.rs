@@ -0,0 +1,166 @@
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use std::io::{self, Read, Write};
+use std::fs::File;
+use std::path::Path;
+use std::thread::panicking;
+
+// Files whose names end in .gz, .xz or .zst are piped through gzip, xz or zstd
+// as they're written and read. Most solvers read compressed DIMACS directly so
+// the formulas in scratch/ can stay compressed.
+pub struct Compression {
+
+}
+
+impl Compression {
+    pub fn command(filename: &str) -> Option<&'static str> {
+        match Path::new(filename).extension()?.to_str()? {
+            "gz" => Some("gzip"),
+            "xz" => Some("xz"),
+            "zst" => Some("zstd"),
+            _ => None,
+        }
+    }
+
+    pub fn create(filename: &String) -> Box<dyn Write> {
+        let file = match File::create(filename) {
+            Ok(file) => file,
+            Err(_) => panic!("Failed to create {}", filename),
+        };
+
+        let command = match Self::command(filename) {
+            Some(command) => command,
+            None => return Box::new(file),
+        };
+
+        Box::new(Self::compressor(command, file, filename))
+    }
+
+    // Lets a child process such as a solver write the file directly. The file
+    // is only complete once the compressor, if there is one, is dropped after
+    // the child exits.
+    pub fn stdio(filename: &String) -> (Stdio, Option<Compressor>) {
+        let file = match File::create(filename) {
+            Ok(file) => file,
+            Err(_) => panic!("Failed to create {}", filename),
+        };
+
+        let command = match Self::command(filename) {
+            Some(command) => command,
+            None => return (Stdio::from(file), None),
+        };
+
+        let mut compressor = Self::compressor(command, file, filename);
+        let stdin = compressor.stdin.take().unwrap();
+
+        (Stdio::from(stdin), Some(compressor))
+    }
+
+    fn compressor(command: &str, file: File, filename: &String) -> Compressor {
+        let mut child = match Command::new(command).arg("-c").stdin(Stdio::piped()).stdout(file).spawn() {
+            Ok(child) => child,
+            Err(_) => panic!("Failed to run {} to compress {}", command, filename),
+        };
+
+        let stdin = child.stdin.take();
+        let filename = filename.clone();
+
+        Compressor { child, stdin, filename }
+    }
+
+    pub fn open(filename: &String) -> Box<dyn Read> {
+        let file = match File::open(filename) {
+            Ok(file) => file,
+            Err(_) => panic!("Failed to read {}", filename),
+        };
+
+        let command = match Self::command(filename) {
+            Some(command) => command,
+            None => return Box::new(file),
+        };
+
+        let mut child = match Command::new(command).arg("-dc").stdin(file).stdout(Stdio::piped()).spawn() {
+            Ok(child) => child,
+            Err(_) => panic!("Failed to run {} to decompress {}", command, filename),
+        };
+
+        let stdout = child.stdout.take();
+
+        Box::new(Decompressor { child, stdout })
+    }
+
+    // Files derived from another, such as a solver's output, keep its
+    // compression: x.dimacs.gz becomes x.dimacs.output.gz.
+    pub fn append(filename: &str, suffix: &str) -> String {
+        match Self::command(filename) {
+            Some(_) => {
+                let (stem, extension) = filename.rsplit_once('.').unwrap();
+                format!("{}{}.{}", stem, suffix, extension)
+            },
+            None => format!("{}{}", filename, suffix),
+        }
+    }
+
+    // The name the file would have uncompressed: x.dimacs.gz becomes x.dimacs.
+    pub fn strip(filename: &str) -> String {
+        match Self::command(filename) {
+            Some(_) => filename.rsplit_once('.').unwrap().0.to_string(),
+            None => filename.to_string(),
+        }
+    }
+}
+
+// The file is only complete once the compressor exits, so dropping the writer
+// closes its input and waits for it.
+pub struct Compressor {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    filename: String,
+}
+
+impl Write for Compressor {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.stdin.as_mut().unwrap().write(buffer)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stdin.as_mut().unwrap().flush()
+    }
+}
+
+// A compressor that fails leaves a truncated file behind, which would be read
+// as a cached formula or answer later.
+impl Drop for Compressor {
+    fn drop(&mut self) {
+        self.stdin.take();
+
+        let status = self.child.wait().unwrap();
+
+        if !status.success() && !panicking() {
+            panic!("Failed to compress {}", self.filename);
+        }
+    }
+}
+
+// Readers may stop early, e.g. once a proof derives the empty clause, so the
+// output is closed first to stop the decompressor rather than wait for it.
+struct Decompressor {
+    child: Child,
+    stdout: Option<ChildStdout>,
+}
+
+impl Read for Decompressor {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        self.stdout.as_mut().unwrap().read(buffer)
+    }
+}
+
+impl Drop for Decompressor {
+    fn drop(&mut self) {
+        self.stdout.take();
+        self.child.wait().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test;
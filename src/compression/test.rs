@@ -0,0 +1,124 @@
+use super::*;
+use std::env::temp_dir;
+use std::fs::{read, remove_file};
+
+type Subject = Compression;
+
+fn filename(name: &str) -> String {
+    temp_dir().join(format!("supersat-compression-{}", name)).to_str().unwrap().to_string()
+}
+
+mod command {
+    use super::*;
+
+    #[test]
+    fn it_chooses_the_compressor_by_extension() {
+        assert_eq!(Subject::command("scratch/x.dimacs.gz"), Some("gzip"));
+        assert_eq!(Subject::command("scratch/x.dimacs.xz"), Some("xz"));
+        assert_eq!(Subject::command("scratch/x.dimacs.zst"), Some("zstd"));
+        assert_eq!(Subject::command("scratch/x.dimacs"), None);
+    }
+}
+
+mod create {
+    use super::*;
+
+    #[test]
+    fn it_writes_plain_files_for_other_extensions() {
+        let filename = filename("plain.dimacs");
+
+        Subject::create(&filename).write_all(b"p cnf 0 0\n").unwrap();
+
+        assert_eq!(read(&filename).unwrap(), b"p cnf 0 0\n");
+        remove_file(&filename).unwrap();
+    }
+
+    #[test]
+    fn it_compresses_the_file_once_the_writer_is_dropped() {
+        let filename = filename("compressed.dimacs.gz");
+
+        Subject::create(&filename).write_all(b"p cnf 0 0\n").unwrap();
+
+        // Gzip files start with the magic number 1f 8b.
+        assert_eq!(&read(&filename).unwrap()[..2], &[0x1f, 0x8b]);
+        remove_file(&filename).unwrap();
+    }
+}
+
+mod stdio {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn it_lets_a_child_process_write_the_compressed_file() {
+        let filename = filename("child.output.gz");
+        let (output, compressor) = Subject::stdio(&filename);
+
+        Command::new("echo").arg("s UNSATISFIABLE").stdout(output).status().unwrap();
+        drop(compressor);
+
+        let mut contents = String::new();
+        Subject::open(&filename).read_to_string(&mut contents).unwrap();
+
+        assert_eq!(contents, "s UNSATISFIABLE\n");
+        remove_file(&filename).unwrap();
+    }
+}
+
+mod open {
+    use super::*;
+
+    #[test]
+    fn it_reads_back_what_was_written() {
+        let filename = filename("round-trip.dimacs.gz");
+        let text = "p cnf 2 1\n1 -2 0\n".repeat(1000);
+
+        Subject::create(&filename).write_all(text.as_bytes()).unwrap();
+
+        let mut contents = String::new();
+        Subject::open(&filename).read_to_string(&mut contents).unwrap();
+
+        assert_eq!(contents, text);
+        remove_file(&filename).unwrap();
+    }
+
+    #[test]
+    fn it_can_stop_reading_before_the_end() {
+        let filename = filename("partial.dimacs.gz");
+        let text = "1 2 0\n".repeat(1_000_000);
+
+        Subject::create(&filename).write_all(text.as_bytes()).unwrap();
+
+        let mut buffer = [0; 6];
+        Subject::open(&filename).read_exact(&mut buffer).unwrap();
+
+        assert_eq!(&buffer, b"1 2 0\n");
+        remove_file(&filename).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to read")]
+    fn it_panics_if_the_file_does_not_exist() {
+        Subject::open(&filename("missing.dimacs.gz"));
+    }
+}
+
+mod append {
+    use super::*;
+
+    #[test]
+    fn it_puts_the_suffix_before_the_compression_extension() {
+        assert_eq!(Subject::append("scratch/x.dimacs.gz", ".output"), "scratch/x.dimacs.output.gz");
+        assert_eq!(Subject::append("scratch/x.dimacs", ".output"), "scratch/x.dimacs.output");
+    }
+}
+
+mod strip {
+    use super::*;
+
+    #[test]
+    fn it_removes_the_compression_extension() {
+        assert_eq!(Subject::strip("scratch/x.dimacs.drat.gz"), "scratch/x.dimacs.drat");
+        assert_eq!(Subject::strip("scratch/x.dimacs.drat"), "scratch/x.dimacs.drat");
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::literal::Literal;
+use crate::clause::Clause;
+use crate::logic::Logic;
+
+// A pseudo-Boolean constraint that the weighted sum of its literals is at
+// least the degree. Coefficients are kept positive by negating literals.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Constraint {
+    terms: Vec<(usize, Literal)>,
+    degree: usize,
+}
+
+impl Constraint {
+    pub fn new(terms: Vec<(usize, Literal)>, degree: usize) -> Self {
+        Self { terms, degree }
+    }
+
+    pub fn at_least(literals: &[Literal], count: usize) -> Self {
+        let terms = literals.iter().map(|l| (1, *l)).collect();
+
+        Self::new(terms, count)
+    }
+
+    pub fn at_most(literals: &[Literal], count: usize) -> Self {
+        let degree = literals.len().saturating_sub(count);
+
+        Self::at_least(&Logic::negate(literals), degree)
+    }
+
+    pub fn from_clause(clause: &Clause) -> Self {
+        let literals = clause.literals().iter().cloned().collect::<Vec<_>>();
+
+        Self::at_least(&literals, 1)
+    }
+}
+
+// The OPB format writes x1 for a positive literal and ~x1 for a negative one.
+impl Display for Constraint {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        for (coefficient, literal) in &self.terms {
+            let sign = if literal.positive { "" } else { "~" };
+            write!(f, "+{} {}x{} ", coefficient, sign, literal.variable)?;
+        }
+
+        write!(f, ">= {} ;", self.degree)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,62 @@
+use super::*;
+
+type Subject = Constraint;
+
+fn literals(numbers: &[isize]) -> Vec<Literal> {
+    numbers.iter().map(|n| Literal::from_dimacs(*n)).collect()
+}
+
+mod at_least {
+    use super::*;
+
+    #[test]
+    fn it_requires_the_count_of_true_literals_to_be_at_least_the_degree() {
+        let subject = Subject::at_least(&literals(&[1, -2, 3]), 2);
+
+        assert_eq!(subject.to_string(), "+1 x1 +1 ~x2 +1 x3 >= 2 ;");
+    }
+}
+
+mod at_most {
+    use super::*;
+
+    #[test]
+    fn it_requires_enough_of_the_literals_to_be_false() {
+        let subject = Subject::at_most(&literals(&[1, -2, 3]), 1);
+
+        assert_eq!(subject.to_string(), "+1 ~x1 +1 x2 +1 ~x3 >= 2 ;");
+    }
+
+    #[test]
+    fn it_is_trivially_true_if_the_count_is_the_number_of_literals() {
+        let subject = Subject::at_most(&literals(&[1, 2]), 3);
+
+        assert_eq!(subject.to_string(), "+1 ~x1 +1 ~x2 >= 0 ;");
+    }
+}
+
+mod from_clause {
+    use super::*;
+
+    #[test]
+    fn it_requires_at_least_one_of_the_literals_to_be_true() {
+        let mut clause = Clause::new();
+
+        clause.add(Literal::from_dimacs(-4));
+        clause.add(Literal::from_dimacs(2));
+
+        assert_eq!(Subject::from_clause(&clause).to_string(), "+1 x2 +1 ~x4 >= 1 ;");
+    }
+}
+
+mod display {
+    use super::*;
+
+    #[test]
+    fn it_formats_the_weighted_terms_as_opb() {
+        let terms = vec![(3, Literal::from_dimacs(5)), (2, Literal::from_dimacs(-6))];
+        let subject = Subject::new(terms, 4);
+
+        assert_eq!(subject.to_string(), "+3 x5 +2 ~x6 >= 4 ;");
+    }
+}
@@ -0,0 +1,64 @@
+use crate::literal::Literal;
+use crate::machine::Machine;
+
+// Splits a query by the symbols that follow the ascending numbers at the start
+// of the string. Each cube fixes the next few symbols so the cubes can be solved
+// independently and the query is satisfiable if any of them are.
+pub struct Cubes {
+
+}
+
+impl Cubes {
+    // Prefixes that would repeat a permutation can't appear in any string so
+    // they're left out.
+    pub fn prefixes(n: usize, depth: usize) -> Vec<Vec<usize>> {
+        let mut prefixes = vec![vec![]];
+
+        for _ in 0..depth {
+            prefixes = prefixes.iter().flat_map(|prefix| {
+                (1..=n).map(move |symbol| {
+                    let mut prefix = prefix.clone();
+                    prefix.push(symbol);
+                    prefix
+                })
+            }).filter(|prefix| !Self::repeats_a_permutation(n, prefix)).collect();
+        }
+
+        prefixes
+    }
+
+    // The literals that fix the prefix after the ascending numbers, which are
+    // given to the solver as assumptions or written as unit clauses.
+    pub fn literals(n: usize, machine: &Machine, prefix: &[usize]) -> Vec<Literal> {
+        prefix.iter().enumerate().flat_map(|(offset, symbol)| {
+            let state = machine.at_time(n + offset).state(&[*symbol]);
+            state.literals().clone()
+        }).collect()
+    }
+
+    fn repeats_a_permutation(n: usize, prefix: &[usize]) -> bool {
+        let string = (1..=n).chain(prefix.iter().cloned()).collect::<Vec<_>>();
+        let mut seen = vec![];
+
+        for window in string.windows(n) {
+            let mut sorted = window.to_vec();
+            sorted.sort_unstable();
+            sorted.dedup();
+
+            if sorted.len() < n {
+                continue;
+            }
+
+            if seen.contains(&window) {
+                return true;
+            }
+
+            seen.push(window);
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod test;
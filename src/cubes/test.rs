@@ -0,0 +1,78 @@
+use super::*;
+use crate::formula::Formula;
+use crate::goal::Goal;
+use crate::bounds::Bounds;
+use crate::logic::Logic;
+use crate::problem::Problem;
+use crate::backend::Backend;
+use crate::cdcl::Cdcl;
+
+type Subject = Cubes;
+
+mod prefixes {
+    use super::*;
+
+    #[test]
+    fn it_returns_every_symbol_for_a_depth_of_one() {
+        assert_eq!(Subject::prefixes(3, 1), vec![vec![1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn it_returns_the_empty_prefix_for_a_depth_of_zero() {
+        assert_eq!(Subject::prefixes(3, 0), vec![Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn it_leaves_out_prefixes_that_repeat_a_permutation() {
+        let prefixes = Subject::prefixes(3, 3);
+
+        assert!(!prefixes.contains(&vec![1, 2, 3]));
+        assert!(prefixes.contains(&vec![1, 2, 1]));
+        assert_eq!(prefixes.len(), 26);
+    }
+}
+
+mod literals {
+    use super::*;
+
+    #[test]
+    fn it_fixes_the_symbols_after_the_ascending_numbers() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(3, 9, &mut formula);
+
+        let mut expected = machine.at_time(3).state(&[1]).literals().clone();
+        expected.extend(machine.at_time(4).state(&[2]).literals());
+
+        assert_eq!(Subject::literals(3, &machine, &[1, 2]), expected);
+    }
+
+    // The cubes cover every string so the query is satisfiable exactly when
+    // one of them is.
+    #[test]
+    fn it_splits_the_query_into_cubes_that_agree_with_the_whole_formula() {
+        for (permutations, expected) in [(3, true), (4, false)] {
+            let length_of_string = permutations + 2;
+
+            let mut formula = Formula::new();
+            let machine = Machine::new(3, length_of_string, &mut formula);
+            let goal = Goal::new(3, length_of_string, &mut formula);
+            let bounds = Bounds::new(3, length_of_string, &[]);
+            let mut logic = Logic::new(&mut formula);
+            let mut problem = Problem::new(3, length_of_string, &machine, &goal, &bounds, &mut logic);
+
+            problem.the_machine_starts_in_the_dead_states();
+            problem.the_machine_changes_state_when_it_reads_input();
+            problem.each_permutation_appears_at_most_once();
+            problem.all_binary_representations_map_to_states();
+            problem.the_string_starts_with_ascending_numbers();
+            problem.the_number_of_wasted_symbols_is_within_bounds();
+
+            let satisfiable = Subject::prefixes(3, 2).iter().any(|prefix| {
+                let cube = Subject::literals(3, &machine, prefix);
+                Cdcl::new().solve(&formula, &cube).is_some()
+            });
+
+            assert_eq!(satisfiable, expected);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::search_strategy::SearchStrategy;
+
+// Starts at the upper bound and backtracks by one permutation until it finds a
+// string. It needs a single satisfiable query but one unsatisfiable query for
+// every permutation the row falls short of the upper bound.
+pub struct Descending;
+
+impl SearchStrategy for Descending {
+    fn search(&self, lower: usize, upper: usize, satisfiable: &mut dyn FnMut(usize) -> bool) -> usize {
+        let mut permutations = upper;
+
+        while permutations > lower && !satisfiable(permutations) {
+            permutations -= 1;
+        }
+
+        permutations
+    }
+}
+
+#[cfg(test)]
+mod test;
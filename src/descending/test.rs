@@ -0,0 +1,23 @@
+use super::*;
+use crate::search_strategy::test;
+
+type Subject = Descending;
+
+fn search(lower: usize, upper: usize, answer: usize) -> (usize, Vec<usize>) {
+    test::search(&Subject {}, lower, upper, answer)
+}
+
+mod search {
+    use super::*;
+
+    #[test]
+    fn it_backtracks_by_one_permutation_from_the_upper_bound() {
+        assert_eq!(search(20, 25, 25), (25, vec![25]));
+        assert_eq!(search(20, 25, 23), (23, vec![25, 24, 23]));
+    }
+
+    #[test]
+    fn it_does_not_query_the_lower_bound() {
+        assert_eq!(search(20, 25, 20), (20, vec![25, 24, 23, 22, 21]));
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum DimacsError {
+    MissingHeader { line: usize },
+    NoHeader,
+    MalformedHeader { line: usize, text: String },
+    DuplicateHeader { line: usize },
+    MalformedLiteral { line: usize, text: String },
+    VariableOutOfRange { line: usize, variable: usize, number_of_variables: usize },
+    UnterminatedClause,
+    WrongNumberOfClauses { expected: usize, found: usize },
+}
+
+impl Display for DimacsError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::MissingHeader { line } =>
+                write!(f, "Line {} comes before the 'p cnf' header", line),
+
+            Self::NoHeader =>
+                write!(f, "There's no 'p cnf' header"),
+
+            Self::MalformedHeader { line, text } =>
+                write!(f, "Line {} isn't a 'p cnf VARIABLES CLAUSES' header: {}", line, text),
+
+            Self::DuplicateHeader { line } =>
+                write!(f, "Line {} repeats the 'p cnf' header", line),
+
+            Self::MalformedLiteral { line, text } =>
+                write!(f, "Line {} contains {}, which isn't a literal", line, text),
+
+            Self::VariableOutOfRange { line, variable, number_of_variables } =>
+                write!(f, "Line {} uses variable {} but the header declares {}", line, variable, number_of_variables),
+
+            Self::UnterminatedClause =>
+                write!(f, "The last clause isn't terminated by 0"),
+
+            Self::WrongNumberOfClauses { expected, found } =>
+                write!(f, "The header declares {} clauses but there are {}", expected, found),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,37 @@
+use super::*;
+
+type Subject = DimacsError;
+
+mod display {
+    use super::*;
+
+    #[test]
+    fn it_explains_what_is_wrong_with_the_header() {
+        let subject = Subject::MissingHeader { line: 2 };
+        assert_eq!(subject.to_string(), "Line 2 comes before the 'p cnf' header");
+
+        let subject = Subject::NoHeader;
+        assert_eq!(subject.to_string(), "There's no 'p cnf' header");
+
+        let subject = Subject::MalformedHeader { line: 1, text: "p dnf 3 2".to_string() };
+        assert_eq!(subject.to_string(), "Line 1 isn't a 'p cnf VARIABLES CLAUSES' header: p dnf 3 2");
+
+        let subject = Subject::DuplicateHeader { line: 4 };
+        assert_eq!(subject.to_string(), "Line 4 repeats the 'p cnf' header");
+    }
+
+    #[test]
+    fn it_explains_what_is_wrong_with_the_clauses() {
+        let subject = Subject::MalformedLiteral { line: 3, text: "x1".to_string() };
+        assert_eq!(subject.to_string(), "Line 3 contains x1, which isn't a literal");
+
+        let subject = Subject::VariableOutOfRange { line: 3, variable: 5, number_of_variables: 4 };
+        assert_eq!(subject.to_string(), "Line 3 uses variable 5 but the header declares 4");
+
+        let subject = Subject::UnterminatedClause;
+        assert_eq!(subject.to_string(), "The last clause isn't terminated by 0");
+
+        let subject = Subject::WrongNumberOfClauses { expected: 3, found: 2 };
+        assert_eq!(subject.to_string(), "The header declares 3 clauses but there are 2");
+    }
+}
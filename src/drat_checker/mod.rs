@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+
+use crate::formula::Formula;
+use crate::proof_error::ProofError;
+use crate::compression::Compression;
+
+const FALSE: u8 = 0;
+const TRUE: u8 = 1;
+const UNASSIGNED: u8 = 2;
+
+// Checks a DRAT proof forwards against the formula it refutes, one lemma at a
+// time, so the proof is streamed rather than loaded. Each lemma must follow by
+// unit propagation (RUP) or be a resolution asymmetric tautology on its first
+// literal (RAT). Literals are coded as in Cdcl.
+pub struct DratChecker {
+    clauses: Vec<Vec<usize>>,
+    deleted: Vec<bool>,
+    watches: Vec<Vec<usize>>,
+    values: Vec<u8>,
+    reasons: Vec<Option<usize>>,
+    trail: Vec<usize>,
+    propagated: usize,
+    lookup: HashMap<Vec<usize>, Vec<usize>>,
+    inconsistent: bool,
+}
+
+impl DratChecker {
+    pub fn new(formula: &Formula) -> Self {
+        let mut checker = Self {
+            clauses: vec![],
+            deleted: vec![],
+            watches: vec![],
+            values: vec![],
+            reasons: vec![],
+            trail: vec![],
+            propagated: 0,
+            lookup: HashMap::new(),
+            inconsistent: false,
+        };
+
+        checker.reserve(formula.number_of_variables());
+
+        for clause in formula.clauses() {
+            let numbers = clause.literals().iter().map(|l| {
+                let number = l.variable.number as isize;
+                if l.positive { number } else { -number }
+            }).collect::<Vec<_>>();
+
+            checker.add(&numbers);
+        }
+
+        checker
+    }
+
+    pub fn check(&mut self, filename: &String) -> Result<(), ProofError> {
+        let file = Compression::open(filename);
+
+        self.check_lines(BufReader::new(file).lines().map(|l| l.unwrap()))
+    }
+
+    pub fn check_lines<I: Iterator<Item=String>>(&mut self, lines: I) -> Result<(), ProofError> {
+        for (index, line) in lines.enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('c') {
+                continue;
+            }
+
+            let (deletion, numbers) = Self::parse(line);
+
+            if deletion {
+                self.delete(&numbers);
+                continue;
+            }
+
+            let codes = Self::codes(&numbers);
+            self.reserve(numbers.iter().map(|n| n.unsigned_abs()).max().unwrap_or(0));
+
+            if !self.rup(&codes) && !self.rat(&codes) {
+                return Err(ProofError::LemmaNotImplied { line: index + 1, lemma: numbers });
+            }
+
+            if codes.is_empty() {
+                return Ok(());
+            }
+
+            self.add(&numbers);
+        }
+
+        match self.inconsistent {
+            true => Ok(()),
+            false => Err(ProofError::EmptyClauseNotDerived),
+        }
+    }
+
+    fn parse(line: &str) -> (bool, Vec<isize>) {
+        let (deletion, rest) = match line.strip_prefix("d ") {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        let numbers = rest.split_whitespace().map(|n| match n.parse::<isize>() {
+            Ok(number) => number,
+            Err(_) => panic!("Failed to parse {} as a DRAT lemma", line),
+        }).collect::<Vec<_>>();
+
+        match numbers.split_last() {
+            Some((0, numbers)) if !numbers.contains(&0) => (deletion, numbers.to_vec()),
+            _ => panic!("Failed to parse {} as a DRAT lemma", line),
+        }
+    }
+
+    fn codes(numbers: &[isize]) -> Vec<usize> {
+        numbers.iter().map(|n| 2 * (n.unsigned_abs() - 1) + (*n < 0) as usize).collect()
+    }
+
+    fn value(values: &[u8], code: usize) -> u8 {
+        match values[code >> 1] {
+            UNASSIGNED => UNASSIGNED,
+            value => value ^ (code & 1) as u8,
+        }
+    }
+
+    fn reserve(&mut self, number_of_variables: usize) {
+        for _ in self.values.len()..number_of_variables {
+            self.watches.push(vec![]);
+            self.watches.push(vec![]);
+            self.values.push(UNASSIGNED);
+            self.reasons.push(None);
+        }
+    }
+
+    fn key(codes: &[usize]) -> Vec<usize> {
+        let mut key = codes.to_vec();
+        key.sort_unstable();
+        key.dedup();
+        key
+    }
+
+    // Clauses are added at the top level so their literals are ordered with
+    // the ones that aren't false first, which keeps the watches valid.
+    fn add(&mut self, numbers: &[isize]) {
+        self.reserve(numbers.iter().map(|n| n.unsigned_abs()).max().unwrap_or(0));
+
+        let mut codes = Self::key(&Self::codes(numbers));
+
+        if codes.iter().any(|c| codes.contains(&(c ^ 1))) {
+            return;
+        }
+
+        let index = self.clauses.len();
+        self.lookup.entry(codes.clone()).or_default().push(index);
+
+        codes.sort_by_key(|c| Self::value(&self.values, *c) == FALSE);
+
+        if codes.len() > 1 {
+            self.watches[codes[0]].push(index);
+            self.watches[codes[1]].push(index);
+        }
+
+        let free = codes.iter().filter(|c| Self::value(&self.values, **c) != FALSE).count();
+        let first = codes.first().cloned();
+
+        self.clauses.push(codes);
+        self.deleted.push(false);
+
+        if self.inconsistent {
+            return;
+        }
+
+        match (free, first) {
+            (0, _) => self.inconsistent = true,
+            (1, Some(code)) if Self::value(&self.values, code) == UNASSIGNED => {
+                self.assign(code, Some(index));
+                self.inconsistent = self.propagate();
+            },
+            _ => {},
+        }
+    }
+
+    // Deleting a clause that is the reason for a top-level assignment would
+    // undo it, so like drat-trim those deletions are ignored.
+    fn delete(&mut self, numbers: &[isize]) {
+        let key = Self::key(&Self::codes(numbers));
+
+        let index = match self.lookup.get(&key).and_then(|indices| indices.last()) {
+            Some(index) => *index,
+            None => return,
+        };
+
+        if key.iter().any(|c| self.reasons.get(c >> 1) == Some(&Some(index))) {
+            return;
+        }
+
+        self.lookup.get_mut(&key).unwrap().pop();
+        self.deleted[index] = true;
+    }
+
+    fn assign(&mut self, code: usize, reason: Option<usize>) {
+        self.values[code >> 1] = TRUE ^ (code & 1) as u8;
+        self.reasons[code >> 1] = reason;
+        self.trail.push(code);
+    }
+
+    // Returns true if propagation leads to a conflict.
+    fn propagate(&mut self) -> bool {
+        while self.propagated < self.trail.len() {
+            let false_code = self.trail[self.propagated] ^ 1;
+            self.propagated += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_code]);
+            let mut kept = 0;
+            let mut conflict = false;
+
+            for position in 0..watchers.len() {
+                let index = watchers[position];
+
+                if self.deleted[index] {
+                    continue;
+                }
+
+                watchers[kept] = index;
+                kept += 1;
+
+                if conflict {
+                    continue;
+                }
+
+                let clause = &mut self.clauses[index];
+
+                if clause[0] == false_code {
+                    clause.swap(0, 1);
+                }
+
+                if Self::value(&self.values, clause[0]) == TRUE {
+                    continue;
+                }
+
+                let values = &self.values;
+                let replacement = (2..clause.len()).find(|k| Self::value(values, clause[*k]) != FALSE);
+
+                if let Some(k) = replacement {
+                    clause.swap(1, k);
+                    self.watches[clause[1]].push(index);
+                    kept -= 1;
+                    continue;
+                }
+
+                match Self::value(&self.values, clause[0]) {
+                    FALSE => conflict = true,
+                    _ => { let code = clause[0]; self.assign(code, Some(index)); },
+                }
+            }
+
+            watchers.truncate(kept);
+            self.watches[false_code] = watchers;
+
+            if conflict {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // Assigns the negation of the lemma and propagates, undoing everything
+    // afterwards so only the top-level assignments remain.
+    fn rup(&mut self, codes: &[usize]) -> bool {
+        if self.inconsistent {
+            return true;
+        }
+
+        let mark = self.trail.len();
+        let mut conflict = false;
+
+        for code in codes {
+            match Self::value(&self.values, *code) {
+                TRUE => { conflict = true; break; },
+                FALSE => {},
+                _ => self.assign(code ^ 1, None),
+            }
+        }
+
+        if !conflict {
+            conflict = self.propagate();
+        }
+
+        for code in self.trail.drain(mark..) {
+            self.values[code >> 1] = UNASSIGNED;
+            self.reasons[code >> 1] = None;
+        }
+
+        self.propagated = mark;
+
+        conflict
+    }
+
+    // Every resolvent on the first literal must be RUP.
+    fn rat(&mut self, codes: &[usize]) -> bool {
+        let pivot = match codes.first() {
+            Some(pivot) => *pivot,
+            None => return false,
+        };
+
+        let candidates = (0..self.clauses.len())
+            .filter(|i| !self.deleted[*i] && self.clauses[*i].contains(&(pivot ^ 1)))
+            .collect::<Vec<_>>();
+
+        candidates.into_iter().all(|index| {
+            let mut resolvent = codes.to_vec();
+            resolvent.extend(self.clauses[index].iter().filter(|c| **c != pivot ^ 1));
+
+            self.rup(&resolvent)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,142 @@
+use super::*;
+use crate::backend::Backend;
+use crate::cdcl::Cdcl;
+use crate::test_formulas::{formula, pigeonhole, Random};
+
+type Subject = DratChecker;
+
+fn lines(text: &str) -> impl Iterator<Item=String> + '_ {
+    text.lines().map(String::from)
+}
+
+mod check_lines {
+    use super::*;
+
+    fn xor() -> Formula {
+        formula(2, &[vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]])
+    }
+
+    #[test]
+    fn it_verifies_a_proof_that_derives_the_empty_clause() {
+        let mut subject = Subject::new(&xor());
+
+        assert_eq!(subject.check_lines(lines("c a comment\n2 0\n0\n")), Ok(()));
+    }
+
+    #[test]
+    fn it_reports_the_first_lemma_that_is_not_implied() {
+        let formula = formula(2, &[vec![1, 2], vec![-1, -2]]);
+        let mut subject = Subject::new(&formula);
+
+        let expected = ProofError::LemmaNotImplied { line: 2, lemma: vec![1] };
+
+        assert_eq!(subject.check_lines(lines("1 2 0\n1 0\n0\n")), Err(expected));
+    }
+
+    #[test]
+    fn it_accepts_lemmas_that_are_rat_on_their_first_literal() {
+        let formula = formula(2, &[vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]]);
+        let mut subject = Subject::new(&formula);
+
+        // Nothing contains -3 so 3 can be introduced.
+        assert_eq!(subject.check_lines(lines("3 0\n2 0\n0\n")), Ok(()));
+    }
+
+    #[test]
+    fn it_forgets_deleted_clauses() {
+        let formula = formula(3, &[vec![1, 2], vec![1, -2], vec![-1, 3], vec![-1, -3]]);
+
+        let mut subject = Subject::new(&formula);
+        assert_eq!(subject.check_lines(lines("1 0\n0\n")), Ok(()));
+
+        let mut subject = Subject::new(&formula);
+        let expected = ProofError::LemmaNotImplied { line: 2, lemma: vec![1] };
+
+        assert_eq!(subject.check_lines(lines("d 1 2 0\n1 0\n0\n")), Err(expected));
+    }
+
+    #[test]
+    fn it_reports_a_proof_that_ends_without_the_empty_clause() {
+        let formula = formula(2, &[vec![1, 2]]);
+        let mut subject = Subject::new(&formula);
+
+        assert_eq!(subject.check_lines(lines("1 2 0\n")), Err(ProofError::EmptyClauseNotDerived));
+    }
+
+    #[test]
+    fn it_accepts_any_proof_of_a_formula_that_propagates_to_a_conflict() {
+        let formula = formula(2, &[vec![1], vec![-1, 2], vec![-2]]);
+        let mut subject = Subject::new(&formula);
+
+        assert_eq!(subject.check_lines(lines("")), Ok(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to parse 1 x 0 as a DRAT lemma")]
+    fn it_panics_if_a_lemma_is_malformed() {
+        Subject::new(&xor()).check_lines(lines("1 x 0\n")).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to parse 1 2 as a DRAT lemma")]
+    fn it_panics_if_a_lemma_is_not_terminated() {
+        Subject::new(&xor()).check_lines(lines("1 2\n")).unwrap();
+    }
+}
+
+// The proofs the built-in solver writes should all be accepted.
+mod check {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::remove_file;
+
+    fn proof(name: &str, formula: &Formula) -> Option<Result<(), ProofError>> {
+        let filename = temp_dir().join(format!("supersat-drat-checker-{}.drat", name));
+        let filename = filename.to_str().unwrap().to_string();
+
+        let satisfiable = Cdcl::with_proof(&filename).solve(formula, &[]).is_some();
+        let result = Subject::new(formula).check(&filename);
+
+        remove_file(&filename).unwrap();
+
+        match satisfiable {
+            true => None,
+            false => Some(result),
+        }
+    }
+
+    #[test]
+    fn it_verifies_the_proofs_written_by_the_builtin_solver() {
+        let formula = formula(20, &pigeonhole(5, 4));
+
+        assert_eq!(proof("pigeonhole", &formula), Some(Ok(())));
+    }
+
+    #[test]
+    fn it_verifies_the_proofs_written_for_random_formulas() {
+        let mut random = Random::new(54321);
+
+        let mut unsatisfiable = 0;
+
+        for _ in 0..100 {
+            let clauses = (0..60).map(|_| random.clause(3, 12)).collect::<Vec<_>>();
+
+            let formula = formula(12, &clauses);
+
+            if let Some(result) = proof("random", &formula) {
+                assert_eq!(result, Ok(()));
+                unsatisfiable += 1;
+            }
+        }
+
+        assert!(unsatisfiable > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to read")]
+    fn it_panics_if_the_proof_cannot_be_read() {
+        let filename = temp_dir().join("supersat-drat-checker-missing.drat");
+
+        Subject::new(&formula(1, &[])).check(&filename.to_str().unwrap().to_string()).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::BTreeSet;
+use itertools::Itertools;
+
+use crate::formula::Formula;
+use crate::machine::Machine;
+use crate::goal::Goal;
+use crate::bounds::Bounds;
+use crate::logic::Logic;
+use crate::problem::Problem;
+use crate::problem_config::ProblemConfig;
+use crate::solver::Solver;
+use crate::backend::Backend;
+use crate::cdcl::Cdcl;
+use crate::model::Model;
+
+pub struct Enumeration {
+    n: usize,
+    length_of_string: usize,
+    config: ProblemConfig,
+}
+
+impl Enumeration {
+    pub fn new(n: usize, length_of_string: usize) -> Self {
+        Self { n, length_of_string, config: ProblemConfig::default() }
+    }
+
+    pub fn set_config(&mut self, config: ProblemConfig) {
+        self.config = config;
+    }
+
+    pub fn solutions(&self, builtin: bool) -> Vec<Vec<usize>> {
+        let (mut formula, machine) = self.formula();
+        let mut solutions = vec![];
+
+        // Blocking clauses are only ever added so one solver can be reused.
+        let mut cdcl = Cdcl::new();
+
+        loop {
+            let model = match builtin {
+                true => cdcl.solve(&formula, &[]),
+                false => self.solve(&formula, solutions.len()),
+            };
+
+            let model = match model {
+                Some(model) => model,
+                None => break,
+            };
+
+            let string = machine.decode(&model);
+
+            // Block the symbols of this solution so the next one is different.
+            let literals = string.iter().enumerate()
+                .flat_map(|(time, symbol)| machine.at_time(time).state(&[*symbol]).literals().clone())
+                .collect::<Vec<_>>();
+
+            Logic::new(&mut formula).contradiction(&literals);
+
+            println!("Found {}", Self::format(&string));
+            solutions.push(string);
+        }
+
+        solutions
+    }
+
+    // The formula changes with every blocking clause so the solver always
+    // runs rather than reading a cached output from an earlier enumeration.
+    fn solve(&self, formula: &Formula, number_of_solutions: usize) -> Option<Model> {
+        let filename = self.filename(number_of_solutions);
+        let output = format!("{}.output", filename);
+
+        println!("Generating {}...", filename);
+        formula.write(&filename);
+
+        Solver::run("lingeling", &filename, &output);
+        Solver::read(&output)
+    }
+
+    // Strings that start with ascending numbers and contain every permutation.
+    pub fn formula(&self) -> (Formula, Machine) {
+        let (n, length_of_string) = (self.n, self.length_of_string);
+
+        let mut formula = Formula::new();
+        let machine = Machine::new(n, length_of_string, &mut formula);
+        let goal = Goal::new(n, length_of_string, &mut formula);
+        let bounds = Bounds::new(n, length_of_string, &[]);
+
+        {
+            let mut logic = Logic::new(&mut formula);
+            let mut problem = Problem::new(n, length_of_string, &machine, &goal, &bounds, &mut logic);
+            problem.set_config(self.config);
+
+            problem.the_machine_starts_in_the_dead_states();
+            problem.the_machine_changes_state_when_it_reads_input();
+            problem.the_string_starts_with_ascending_numbers();
+            problem.all_binary_representations_map_to_states();
+
+            for name in (1..=n).permutations(n) {
+                problem.the_permutation_appears(&name);
+            }
+        }
+
+        (formula, machine)
+    }
+
+    // Strings are the same modulo symmetry if they can be relabelled and/or
+    // reversed into each other so only keep one from each class.
+    pub fn distinct(&self, solutions: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        let canonical = solutions.iter()
+            .map(|s| Self::canonical(s))
+            .collect::<BTreeSet<_>>();
+
+        canonical.into_iter().collect()
+    }
+
+    pub fn format(string: &[usize]) -> String {
+        string.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn canonical(string: &[usize]) -> Vec<usize> {
+        let reversed = string.iter().rev().cloned().collect::<Vec<_>>();
+
+        Self::relabel(string).min(Self::relabel(&reversed))
+    }
+
+    // Renames the symbols in the order they first appear.
+    fn relabel(string: &[usize]) -> Vec<usize> {
+        let mut labels = vec![];
+
+        string.iter().map(|symbol| {
+            match labels.iter().position(|l| l == symbol) {
+                Some(index) => index + 1,
+                None => { labels.push(*symbol); labels.len() },
+            }
+        }).collect()
+    }
+
+    fn filename(&self, number_of_solutions: usize) -> String {
+        format!(
+            "scratch/{}-symbols-{}-length-solution-{}.dimacs",
+            self.n, self.length_of_string, number_of_solutions + 1,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test;
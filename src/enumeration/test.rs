@@ -0,0 +1,106 @@
+use super::*;
+
+type Subject = Enumeration;
+
+mod new {
+    use super::*;
+
+    #[test]
+    fn it_builds_the_struct() {
+        let subject = Subject::new(4, 33);
+
+        assert_eq!(subject.n, 4);
+        assert_eq!(subject.length_of_string, 33);
+    }
+}
+
+mod formula {
+    use super::*;
+
+    #[test]
+    fn it_records_the_config_in_the_formula() {
+        let config = ProblemConfig { dead_state_transitions: true, ..ProblemConfig::default() };
+
+        let mut subject = Subject::new(2, 3);
+        subject.set_config(config);
+
+        let (formula, _) = subject.formula();
+
+        assert_eq!(formula.comments(), [format!("config {}", config)]);
+    }
+}
+
+mod solutions {
+    use super::*;
+
+    #[test]
+    fn it_finds_every_string_that_starts_with_ascending_numbers() {
+        let subject = Subject::new(3, 9);
+        assert_eq!(subject.solutions(true), &[vec![1, 2, 3, 1, 2, 1, 3, 2, 1]]);
+
+        let subject = Subject::new(3, 10);
+        let solutions = subject.solutions(true);
+
+        assert_eq!(solutions.len(), 7);
+        assert_eq!(subject.distinct(&solutions).len(), 5);
+
+        let subject = Subject::new(3, 11);
+        let solutions = subject.solutions(true);
+
+        assert_eq!(solutions.len(), 35);
+        assert_eq!(subject.distinct(&solutions).len(), 27);
+    }
+}
+
+mod distinct {
+    use super::*;
+
+    #[test]
+    fn it_removes_solutions_that_are_relabellings_or_reversals_of_each_other() {
+        let subject = Subject::new(3, 10);
+
+        let solutions = vec![
+            vec![1, 2, 3, 1, 2, 1, 3, 2, 1, 3],
+            vec![3, 1, 2, 3, 1, 2, 1, 3, 2, 1], // the reversal
+            vec![2, 1, 3, 2, 1, 2, 3, 1, 2, 3], // a relabelling
+            vec![1, 2, 3, 1, 2, 1, 3, 2, 1, 2],
+        ];
+
+        // The smallest relabelling of each string or its reversal is kept.
+        assert_eq!(subject.distinct(&solutions), &[
+            vec![1, 2, 1, 3, 2, 1, 2, 3, 1, 2],
+            vec![1, 2, 3, 1, 2, 1, 3, 2, 1, 3],
+        ]);
+    }
+}
+
+mod relabel {
+    use super::*;
+
+    #[test]
+    fn it_renames_the_symbols_in_the_order_they_first_appear() {
+        assert_eq!(Subject::relabel(&[3, 1, 3, 2]), &[1, 2, 1, 3]);
+        assert_eq!(Subject::relabel(&[1, 2, 3]), &[1, 2, 3]);
+    }
+}
+
+mod format {
+    use super::*;
+
+    #[test]
+    fn it_formats_the_string_as_digits() {
+        assert_eq!(Subject::format(&[1, 2, 3, 1]), "1231");
+    }
+}
+
+mod filename {
+    use super::*;
+
+    #[test]
+    fn it_numbers_the_formulas_by_the_solution_they_search_for() {
+        let subject = Subject::new(4, 33);
+
+        assert_eq!(subject.filename(0), "scratch/4-symbols-33-length-solution-1.dimacs");
+        assert_eq!(subject.filename(2), "scratch/4-symbols-33-length-solution-3.dimacs");
+    }
+}
@@ -0,0 +1,94 @@
+use crate::literal::Literal;
+
+// A boolean expression over literals that Logic turns into clauses. The
+// constructors flatten and sort their operands so that an expression that's
+// built twice is equal to itself and can share the literal Logic gives it.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Expression {
+    Literal(Literal),
+    Not(Box<Expression>),
+    And(Vec<Expression>),
+    Or(Vec<Expression>),
+    Xor(Box<Expression>, Box<Expression>),
+    Ite(Box<Expression>, Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    pub fn literal(literal: Literal) -> Self {
+        Self::Literal(literal)
+    }
+
+    pub fn literals(literals: &[Literal]) -> Vec<Self> {
+        literals.iter().map(|l| Self::literal(*l)).collect()
+    }
+
+    pub fn not(expression: Self) -> Self {
+        match expression {
+            Self::Literal(literal) => Self::Literal(literal.negate()),
+            Self::Not(expression) => *expression,
+            expression => Self::Not(Box::new(expression)),
+        }
+    }
+
+    // An empty conjunction is true.
+    pub fn and(operands: Vec<Self>) -> Self {
+        let mut operands = operands.into_iter().flat_map(|operand| match operand {
+            Self::And(operands) => operands,
+            operand => vec![operand],
+        }).collect::<Vec<_>>();
+
+        operands.sort();
+        operands.dedup();
+
+        if operands.len() == 1 {
+            return operands.remove(0);
+        }
+
+        Self::And(operands)
+    }
+
+    // An empty disjunction is false.
+    pub fn or(operands: Vec<Self>) -> Self {
+        let mut operands = operands.into_iter().flat_map(|operand| match operand {
+            Self::Or(operands) => operands,
+            operand => vec![operand],
+        }).collect::<Vec<_>>();
+
+        operands.sort();
+        operands.dedup();
+
+        if operands.len() == 1 {
+            return operands.remove(0);
+        }
+
+        Self::Or(operands)
+    }
+
+    pub fn xor(a: Self, b: Self) -> Self {
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+
+        Self::Xor(Box::new(a), Box::new(b))
+    }
+
+    pub fn iff(a: Self, b: Self) -> Self {
+        Self::not(Self::xor(a, b))
+    }
+
+    pub fn ite(condition: Self, then: Self, otherwise: Self) -> Self {
+        Self::Ite(Box::new(condition), Box::new(then), Box::new(otherwise))
+    }
+
+    // Bit-vectors are equal if each pair of bits is.
+    pub fn equal(a: &[Literal], b: &[Literal]) -> Self {
+        if a.len() != b.len() {
+            panic!("Failed to compare bit-vectors of lengths {} and {}", a.len(), b.len());
+        }
+
+        Self::and(a.iter().zip(b).map(|(a, b)| {
+            Self::iff(Self::literal(*a), Self::literal(*b))
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,107 @@
+use super::*;
+use crate::variable::Variable;
+
+type Subject = Expression;
+
+fn literal(number: isize) -> Subject {
+    Subject::literal(Literal::from_dimacs(number))
+}
+
+mod not {
+    use super::*;
+
+    #[test]
+    fn it_negates_literals_directly() {
+        assert_eq!(Subject::not(literal(3)), literal(-3));
+    }
+
+    #[test]
+    fn it_removes_double_negations() {
+        let expression = Subject::xor(literal(1), literal(2));
+
+        assert_eq!(Subject::not(Subject::not(expression.clone())), expression);
+    }
+}
+
+mod and {
+    use super::*;
+
+    #[test]
+    fn it_flattens_sorts_and_deduplicates_the_operands() {
+        let nested = Subject::and(vec![literal(3), literal(1)]);
+        let subject = Subject::and(vec![literal(2), nested, literal(1)]);
+
+        assert_eq!(subject, Subject::And(vec![literal(1), literal(2), literal(3)]));
+    }
+
+    #[test]
+    fn it_is_the_operand_if_there_is_only_one() {
+        assert_eq!(Subject::and(vec![literal(5), literal(5)]), literal(5));
+    }
+
+    #[test]
+    fn it_is_equal_regardless_of_the_order_of_the_operands() {
+        let a = Subject::and(vec![literal(1), Subject::or(vec![literal(2), literal(-3)])]);
+        let b = Subject::and(vec![Subject::or(vec![literal(-3), literal(2)]), literal(1)]);
+
+        assert_eq!(a, b);
+    }
+}
+
+mod or {
+    use super::*;
+
+    #[test]
+    fn it_flattens_sorts_and_deduplicates_the_operands() {
+        let nested = Subject::or(vec![literal(-4), literal(2)]);
+        let subject = Subject::or(vec![nested, literal(2), literal(1)]);
+
+        assert_eq!(subject, Subject::Or(vec![literal(1), literal(2), literal(-4)]));
+    }
+}
+
+mod xor {
+    use super::*;
+
+    #[test]
+    fn it_is_equal_regardless_of_the_order_of_the_operands() {
+        assert_eq!(Subject::xor(literal(2), literal(1)), Subject::xor(literal(1), literal(2)));
+    }
+}
+
+mod iff {
+    use super::*;
+
+    #[test]
+    fn it_is_the_negation_of_xor() {
+        let subject = Subject::iff(literal(1), literal(2));
+
+        assert_eq!(subject, Subject::not(Subject::xor(literal(1), literal(2))));
+    }
+}
+
+mod equal {
+    use super::*;
+
+    #[test]
+    fn it_is_the_conjunction_of_the_bits_being_equal() {
+        let a = [Literal::positive(Variable::new(1)), Literal::negative(Variable::new(2))];
+        let b = [Literal::positive(Variable::new(3)), Literal::positive(Variable::new(4))];
+
+        let expected = Subject::and(vec![
+            Subject::iff(literal(1), literal(3)),
+            Subject::iff(literal(-2), literal(4)),
+        ]);
+
+        assert_eq!(Subject::equal(&a, &b), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to compare bit-vectors of lengths 1 and 2")]
+    fn it_panics_if_the_lengths_differ() {
+        Subject::equal(
+            &[Literal::positive(Variable::new(1))],
+            &[Literal::positive(Variable::new(2)), Literal::positive(Variable::new(3))],
+        );
+    }
+}
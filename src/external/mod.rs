@@ -0,0 +1,38 @@
+use crate::formula::Formula;
+use crate::literal::Literal;
+use crate::model::Model;
+use crate::backend::Backend;
+use crate::solver::Solver;
+use crate::compression::Compression;
+
+// Runs a solver that reads iCNF, such as ilingeling, as a separate process.
+// Solvers like ilingeling read all of their input before they start on the
+// assumptions, so each query writes the whole formula with its own 'a' line
+// and runs the solver once on it.
+pub struct External {
+    command: String,
+    filename: String,
+}
+
+impl External {
+    pub fn new(command: &str, filename: &str) -> Self {
+        let command = command.to_string();
+        let filename = filename.to_string();
+
+        Self { command, filename }
+    }
+}
+
+impl Backend for External {
+    fn solve(&mut self, formula: &Formula, assumptions: &[Literal]) -> Option<Model> {
+        let output = Compression::append(&self.filename, ".output");
+
+        formula.write_incremental(&self.filename, &[assumptions.to_vec()]);
+        Solver::run(&self.command, &self.filename, &output);
+
+        Solver::read(&output)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,67 @@
+use super::*;
+use std::env::temp_dir;
+use std::fs::{read_to_string, remove_file, write, set_permissions, Permissions};
+use std::os::unix::fs::PermissionsExt;
+
+use crate::clause::Clause;
+use crate::variable::Variable;
+
+type Subject = External;
+
+// A solver that answers from the assumptions in the file it's given.
+const SOLVER: &str = "#!/bin/sh
+if grep -q '^a 2 0' \"$1\"; then echo 's UNSATISFIABLE'; else echo 's SATISFIABLE'; echo 'v 1 -2 0'; fi
+";
+
+fn paths(name: &str) -> (String, String) {
+    let solver = temp_dir().join(format!("supersat-external-{}.sh", name));
+    let solver = solver.to_str().unwrap().to_string();
+
+    write(&solver, SOLVER).unwrap();
+    set_permissions(&solver, Permissions::from_mode(0o755)).unwrap();
+
+    let filename = temp_dir().join(format!("supersat-external-{}.icnf", name));
+    let filename = filename.to_str().unwrap().to_string();
+
+    (solver, filename)
+}
+
+mod solve {
+    use super::*;
+
+    #[test]
+    fn it_writes_the_whole_formula_with_the_assumptions_for_each_query() {
+        let (solver, filename) = paths("solve");
+
+        let mut formula = Formula::new();
+        let a = formula.new_variable();
+        let b = formula.new_variable();
+
+        let mut clause = Clause::new();
+        clause.add(Literal::positive(a));
+        formula.add_clause(clause);
+        formula.add_comment("config test".to_string());
+
+        let mut subject = Subject::new(&solver, &filename);
+
+        let model = subject.solve(&formula, &[Literal::negative(b)]).unwrap();
+        assert!(model.satisfies(&[Literal::positive(a)]));
+
+        let mut clause = Clause::new();
+        clause.add(Literal::negative(Variable::new(1)));
+        clause.add(Literal::positive(b));
+        formula.add_clause(clause);
+
+        assert_eq!(subject.solve(&formula, &[Literal::positive(b)]), None);
+
+        let contents = read_to_string(&filename).unwrap();
+
+        assert!(contents.starts_with("c config test\np inccnf\n"));
+        assert!(contents.contains("1 0\n") && contents.contains("-1 2 0\n"));
+        assert!(contents.ends_with("a 2 0\n") && !contents.contains("a -2 0"));
+
+        remove_file(&solver).unwrap();
+        remove_file(&filename).unwrap();
+        remove_file(format!("{}.output", filename)).unwrap();
+    }
+}
@@ -1,24 +1,29 @@
 use std::collections::HashSet;
-use std::fmt::{Display, Formatter, Result};
-use std::io::{Write, BufWriter};
-use std::fs::File;
+use std::fmt::{self, Display, Formatter};
+use std::io::{Write, BufWriter, BufRead, BufReader};
 use std::path::Path;
 
 use crate::variable::Variable;
+use crate::literal::Literal;
 use crate::clause::Clause;
+use crate::constraint::Constraint;
+use crate::dimacs_error::DimacsError;
+use crate::compression::Compression;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Formula {
     variables: HashSet<Variable>,
     clauses: HashSet<Clause>,
+    comments: Vec<String>,
 }
 
 impl Formula {
     pub fn new() -> Self {
         let variables = HashSet::new();
         let clauses = HashSet::new();
+        let comments = vec![];
 
-        Self { variables, clauses }
+        Self { variables, clauses, comments }
     }
 
     pub fn new_variable(&mut self) -> Variable {
@@ -34,6 +39,26 @@ impl Formula {
         self.clauses.insert(clause);
     }
 
+    // Comments are written before the header of DIMACS files, e.g. to record
+    // how the formula was encoded.
+    pub fn add_comment(&mut self, comment: String) {
+        if !self.comments.contains(&comment) {
+            self.comments.push(comment);
+        }
+    }
+
+    pub fn number_of_variables(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn clauses(&self) -> &HashSet<Clause> {
+        &self.clauses
+    }
+
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
     pub fn generate<F: Fn(&mut Self)>(filename: &String, callback: F) {
         if Path::new(filename).exists() {
             return;
@@ -42,20 +67,231 @@ impl Formula {
         let mut formula = Self::new();
 
         callback(&mut formula);
+        formula.write(filename);
+    }
+
+    pub fn read(filename: &String) -> Self {
+        let file = Compression::open(filename);
+
+        match Self::parse(BufReader::new(file).lines().map(|l| l.unwrap())) {
+            Ok(formula) => formula,
+            Err(error) => panic!("Failed to parse {}: {}", filename, error),
+        }
+    }
+
+    // Clauses may span several lines, or share one, since only the 0 ends them.
+    pub fn parse<I: Iterator<Item=String>>(lines: I) -> Result<Self, DimacsError> {
+        let mut formula = Self::new();
+        let mut header = None;
+        let mut clause = Clause::new();
+        let mut open = false;
+        let mut found = 0;
+
+        for (index, line) in lines.enumerate() {
+            let line = line.trim();
+            let number = index + 1;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            // Comments are kept so that they're written again, e.g. the config,
+            // but projections are written by write_projected.
+            if let Some(comment) = line.strip_prefix('c') {
+                if !comment.starts_with(" ind ") && !comment.trim().is_empty() {
+                    formula.add_comment(comment.trim().to_string());
+                }
+
+                continue;
+            }
+
+            // Some benchmarks end with a % line.
+            if line.starts_with('%') {
+                break;
+            }
+
+            if line.starts_with('p') {
+                if header.is_some() {
+                    return Err(DimacsError::DuplicateHeader { line: number });
+                }
+
+                let malformed = || DimacsError::MalformedHeader { line: number, text: line.to_string() };
+                let parts = line.split_whitespace().collect::<Vec<_>>();
 
-        let file = File::create(filename).unwrap();
+                let (variables, clauses) = match parts.as_slice() {
+                    ["p", "cnf", variables, clauses] => (variables.parse::<usize>(), clauses.parse::<usize>()),
+                    _ => return Err(malformed()),
+                };
+
+                let (variables, clauses) = match (variables, clauses) {
+                    (Ok(variables), Ok(clauses)) => (variables, clauses),
+                    _ => return Err(malformed()),
+                };
+
+                for _ in 0..variables {
+                    formula.new_variable();
+                }
+
+                header = Some((variables, clauses));
+                continue;
+            }
+
+            let (variables, _) = match header {
+                Some(header) => header,
+                None => return Err(DimacsError::MissingHeader { line: number }),
+            };
+
+            for text in line.split_whitespace() {
+                let literal = match text.parse::<isize>() {
+                    Ok(literal) => literal,
+                    Err(_) => return Err(DimacsError::MalformedLiteral { line: number, text: text.to_string() }),
+                };
+
+                if literal == 0 {
+                    formula.add_clause(std::mem::replace(&mut clause, Clause::new()));
+                    open = false;
+                    found += 1;
+                    continue;
+                }
+
+                let variable = literal.unsigned_abs();
+
+                if variable > variables {
+                    return Err(DimacsError::VariableOutOfRange { line: number, variable, number_of_variables: variables });
+                }
+
+                clause.add(Literal::from_dimacs(literal));
+                open = true;
+            }
+        }
+
+        let expected = match header {
+            Some((_, clauses)) => clauses,
+            None => return Err(DimacsError::NoHeader),
+        };
+
+        if open {
+            return Err(DimacsError::UnterminatedClause);
+        }
+
+        if found != expected {
+            return Err(DimacsError::WrongNumberOfClauses { expected, found });
+        }
+
+        Ok(formula)
+    }
+
+    pub fn write(&self, filename: &String) {
+        self.write_projected(filename, &[]);
+    }
+
+    // Model counters only count distinct assignments to the variables listed
+    // in the 'c ind' lines when they're present.
+    pub fn write_projected(&self, filename: &String, projection: &[Variable]) {
+        let file = Compression::create(filename);
         let mut buffer = BufWriter::new(file);
 
-        write!(buffer, "p cnf {} {}\n", formula.variables.len(), formula.clauses.len()).unwrap();
+        self.write_comments(&mut buffer, "c");
+        write!(buffer, "p cnf {} {}\n", self.variables.len(), self.clauses.len()).unwrap();
+
+        for chunk in projection.chunks(10) {
+            let numbers = chunk.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+            write!(buffer, "c ind {} 0\n", numbers.join(" ")).unwrap();
+        }
 
-        for clause in &formula.clauses {
+        for clause in &self.clauses {
+            write!(buffer, "{}\n", clause).unwrap();
+        }
+    }
+
+    // Cubes are written as unit clauses so each one can be given to a solver
+    // as a separate job.
+    pub fn write_cube(&self, filename: &String, cube: &[Literal]) {
+        let file = Compression::create(filename);
+        let mut buffer = BufWriter::new(file);
+
+        self.write_comments(&mut buffer, "c");
+        write!(buffer, "p cnf {} {}\n", self.variables.len(), self.clauses.len() + cube.len()).unwrap();
+
+        for clause in &self.clauses {
+            write!(buffer, "{}\n", clause).unwrap();
+        }
+
+        for literal in cube {
+            write!(buffer, "{} 0\n", literal).unwrap();
+        }
+    }
+
+    // The iCNF format asks the solver to solve the formula once for each line
+    // of assumptions, carrying what it learns across them.
+    pub fn write_incremental(&self, filename: &String, assumptions: &[Vec<Literal>]) {
+        let file = Compression::create(filename);
+        let mut buffer = BufWriter::new(file);
+
+        self.write_comments(&mut buffer, "c");
+        write!(buffer, "p inccnf\n").unwrap();
+
+        for clause in &self.clauses {
             write!(buffer, "{}\n", clause).unwrap();
         }
+
+        for literals in assumptions {
+            let literals = literals.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+            write!(buffer, "a {} 0\n", literals.join(" ")).unwrap();
+        }
+    }
+
+    // MaxSAT solvers satisfy every hard clause, which are weighted with the
+    // top weight, and as many of the unit soft clauses as they can.
+    pub fn write_wcnf(&self, filename: &String, soft: &[Literal]) {
+        let file = Compression::create(filename);
+        let mut buffer = BufWriter::new(file);
+
+        let top = soft.len() + 1;
+        let number_of_clauses = self.clauses.len() + soft.len();
+
+        self.write_comments(&mut buffer, "c");
+        write!(buffer, "p wcnf {} {} {}\n", self.variables.len(), number_of_clauses, top).unwrap();
+
+        for clause in &self.clauses {
+            write!(buffer, "{} {}\n", top, clause).unwrap();
+        }
+
+        for literal in soft {
+            write!(buffer, "1 {} 0\n", literal).unwrap();
+        }
+    }
+
+    fn write_comments<W: Write>(&self, buffer: &mut W, prefix: &str) {
+        for comment in &self.comments {
+            write!(buffer, "{} {}\n", prefix, comment).unwrap();
+        }
+    }
+
+    // Pseudo-Boolean solvers read the clauses as constraints that at least one
+    // literal is true, alongside any other constraints on the same variables.
+    pub fn write_opb(&self, filename: &String, constraints: &[Constraint]) {
+        let file = Compression::create(filename);
+        let mut buffer = BufWriter::new(file);
+
+        let number_of_constraints = self.clauses.len() + constraints.len();
+        write!(buffer, "* #variable= {} #constraint= {}\n", self.variables.len(), number_of_constraints).unwrap();
+
+        // The header has to be the first line so the comments follow it.
+        self.write_comments(&mut buffer, "*");
+
+        for clause in &self.clauses {
+            write!(buffer, "{}\n", Constraint::from_clause(clause)).unwrap();
+        }
+
+        for constraint in constraints {
+            write!(buffer, "{}\n", constraint).unwrap();
+        }
     }
 }
 
 impl Display for Formula {
-    fn fmt(&self, f: &mut Formatter) -> Result {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "p cnf {} {}\n", self.variables.len(), self.clauses.len())?;
 
         for clause in &self.clauses {
@@ -81,6 +81,349 @@ mod add_clause {
     }
 }
 
+mod parse {
+    use super::*;
+
+    fn lines(text: &str) -> impl Iterator<Item=String> + '_ {
+        text.lines().map(String::from)
+    }
+
+    fn formula(number_of_variables: usize, clauses: &[&[isize]]) -> Subject {
+        let mut formula = Subject::new();
+
+        for _ in 0..number_of_variables {
+            formula.new_variable();
+        }
+
+        for numbers in clauses {
+            let mut clause = Clause::new();
+
+            for number in numbers.iter() {
+                clause.add(Literal::from_dimacs(*number));
+            }
+
+            formula.add_clause(clause);
+        }
+
+        formula
+    }
+
+    #[test]
+    fn it_reads_back_the_formula_that_was_written() {
+        let subject = formula(3, &[&[1, -2], &[2, 3], &[-3], &[]]);
+
+        assert_eq!(Subject::parse(lines(&subject.to_string())), Ok(subject));
+    }
+
+    #[test]
+    fn it_keeps_comments_and_allows_clauses_to_span_lines() {
+        let text = "c a comment\np cnf 3 3\nc ind 1 2 0\n1 -2\n0 2 3 0\n\n-3 0\n%\n0\n";
+
+        let mut expected = formula(3, &[&[1, -2], &[2, 3], &[-3]]);
+        expected.add_comment("a comment".to_string());
+
+        assert_eq!(Subject::parse(lines(text)), Ok(expected));
+    }
+
+    #[test]
+    fn it_counts_repeated_clauses_towards_the_header() {
+        let text = "p cnf 2 2\n1 2 0\n2 1 0\n";
+
+        assert_eq!(Subject::parse(lines(text)), Ok(formula(2, &[&[1, 2]])));
+    }
+
+    #[test]
+    fn it_rejects_a_missing_or_malformed_header() {
+        assert_eq!(Subject::parse(lines("1 2 0\n")), Err(DimacsError::MissingHeader { line: 1 }));
+        assert_eq!(Subject::parse(lines("c empty\n")), Err(DimacsError::NoHeader));
+
+        let expected = DimacsError::MalformedHeader { line: 1, text: "p cnf 3".to_string() };
+        assert_eq!(Subject::parse(lines("p cnf 3\n")), Err(expected));
+
+        let expected = DimacsError::MalformedHeader { line: 1, text: "p cnf three 1".to_string() };
+        assert_eq!(Subject::parse(lines("p cnf three 1\n")), Err(expected));
+
+        let expected = DimacsError::DuplicateHeader { line: 2 };
+        assert_eq!(Subject::parse(lines("p cnf 1 0\np cnf 1 0\n")), Err(expected));
+    }
+
+    #[test]
+    fn it_rejects_malformed_clauses() {
+        let expected = DimacsError::MalformedLiteral { line: 2, text: "x1".to_string() };
+        assert_eq!(Subject::parse(lines("p cnf 2 1\nx1 2 0\n")), Err(expected));
+
+        let expected = DimacsError::VariableOutOfRange { line: 3, variable: 3, number_of_variables: 2 };
+        assert_eq!(Subject::parse(lines("p cnf 2 2\n1 0\n-3 0\n")), Err(expected));
+
+        assert_eq!(Subject::parse(lines("p cnf 2 1\n1 2\n")), Err(DimacsError::UnterminatedClause));
+
+        let expected = DimacsError::WrongNumberOfClauses { expected: 3, found: 2 };
+        assert_eq!(Subject::parse(lines("p cnf 2 3\n1 0\n2 0\n")), Err(expected));
+    }
+}
+
+mod read {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{remove_file, write};
+
+    #[test]
+    fn it_reads_a_formula_from_a_file() {
+        let mut subject = Subject::new();
+        let a = subject.new_variable();
+        let mut clause = Clause::new();
+
+        clause.add(Literal::negative(a));
+        subject.add_clause(clause);
+
+        let filename = temp_dir().join("supersat-formula-read.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        subject.write(&filename);
+
+        assert_eq!(Subject::read(&filename), subject);
+        remove_file(&filename).unwrap();
+    }
+
+    #[test]
+    fn it_keeps_the_comments_when_the_formula_is_written_again() {
+        let mut subject = Subject::new();
+        subject.new_variable();
+        subject.add_comment("config foo=true".to_string());
+
+        let filename = temp_dir().join("supersat-formula-read-comments.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        subject.write(&filename);
+        Subject::read(&filename).write(&filename);
+
+        assert_eq!(Subject::read(&filename).comments(), ["config foo=true".to_string()]);
+        remove_file(&filename).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Line 2 uses variable 2 but the header declares 1")]
+    fn it_panics_with_the_reason_the_file_is_malformed() {
+        let filename = temp_dir().join("supersat-formula-read-malformed.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        write(&filename, "p cnf 1 1\n2 0\n").unwrap();
+        Subject::read(&filename);
+    }
+}
+
+mod write {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+
+    #[test]
+    fn it_writes_the_formula_to_a_file_as_dimacs() {
+        let mut subject = Subject::new();
+
+        let a = subject.new_variable();
+        let mut clause = Clause::new();
+
+        clause.add(Literal::negative(a));
+        subject.add_clause(clause);
+
+        let filename = temp_dir().join("supersat-formula-write.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        subject.write(&filename);
+
+        assert_eq!(read_to_string(&filename).unwrap(), "p cnf 1 1\n-1 0\n");
+        remove_file(&filename).unwrap();
+    }
+
+    #[test]
+    fn it_writes_each_comment_once_before_the_header() {
+        let mut subject = Subject::new();
+
+        subject.new_variable();
+        subject.add_comment("config foo=true".to_string());
+        subject.add_comment("config foo=true".to_string());
+        subject.add_comment("bar".to_string());
+
+        let filename = temp_dir().join("supersat-formula-write-comments.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        subject.write(&filename);
+
+        assert_eq!(read_to_string(&filename).unwrap(), "c config foo=true\nc bar\np cnf 1 0\n");
+        remove_file(&filename).unwrap();
+    }
+}
+
+mod write_cube {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+
+    #[test]
+    fn it_writes_the_cube_as_unit_clauses_after_the_formula() {
+        let mut subject = Subject::new();
+
+        let a = subject.new_variable();
+        let b = subject.new_variable();
+        let mut clause = Clause::new();
+
+        clause.add(Literal::positive(a));
+        clause.add(Literal::positive(b));
+        subject.add_clause(clause);
+
+        let filename = temp_dir().join("supersat-formula-write-cube.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        subject.write_cube(&filename, &[Literal::negative(a)]);
+
+        assert_eq!(read_to_string(&filename).unwrap(), "p cnf 2 2\n1 2 0\n-1 0\n");
+        remove_file(&filename).unwrap();
+    }
+}
+
+mod write_projected {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+
+    #[test]
+    fn it_writes_the_projection_as_c_ind_lines_of_up_to_ten_variables() {
+        let mut subject = Subject::new();
+
+        let variables = (0..12).map(|_| subject.new_variable()).collect::<Vec<_>>();
+
+        let filename = temp_dir().join("supersat-formula-write-projected.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        subject.write_projected(&filename, &variables);
+
+        assert_eq!(read_to_string(&filename).unwrap(), [
+            "p cnf 12 0",
+            "c ind 1 2 3 4 5 6 7 8 9 10 0",
+            "c ind 11 12 0",
+            "",
+        ].join("\n"));
+
+        remove_file(&filename).unwrap();
+    }
+}
+
+mod write_incremental {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+
+    #[test]
+    fn it_writes_the_formula_as_icnf_with_a_line_for_each_set_of_assumptions() {
+        let mut subject = Subject::new();
+
+        let a = subject.new_variable();
+        let b = subject.new_variable();
+
+        let mut clause = Clause::new();
+
+        clause.add(Literal::negative(a));
+        clause.add(Literal::positive(b));
+
+        subject.add_clause(clause);
+        subject.add_comment("config foo=true".to_string());
+
+        let filename = temp_dir().join("supersat-formula-write-incremental.icnf");
+        let filename = filename.to_str().unwrap().to_string();
+
+        let assumptions = vec![
+            vec![Literal::positive(a)],
+            vec![Literal::positive(a), Literal::negative(b)],
+        ];
+
+        subject.write_incremental(&filename, &assumptions);
+
+        assert_eq!(read_to_string(&filename).unwrap(), [
+            "c config foo=true",
+            "p inccnf",
+            "-1 2 0",
+            "a 1 0",
+            "a 1 -2 0",
+            "",
+        ].join("\n"));
+
+        remove_file(&filename).unwrap();
+    }
+}
+
+mod write_wcnf {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+
+    #[test]
+    fn it_writes_the_clauses_as_hard_and_the_literals_as_soft() {
+        let mut subject = Subject::new();
+
+        let a = subject.new_variable();
+        let b = subject.new_variable();
+        let mut clause = Clause::new();
+
+        clause.add(Literal::negative(a));
+        clause.add(Literal::negative(b));
+        subject.add_clause(clause);
+        subject.add_comment("config foo=true".to_string());
+
+        let filename = temp_dir().join("supersat-formula-write-wcnf.wcnf");
+        let filename = filename.to_str().unwrap().to_string();
+
+        subject.write_wcnf(&filename, &[Literal::positive(a), Literal::positive(b)]);
+
+        assert_eq!(read_to_string(&filename).unwrap(), [
+            "c config foo=true",
+            "p wcnf 2 3 3",
+            "3 -1 -2 0",
+            "1 1 0",
+            "1 2 0",
+            "",
+        ].join("\n"));
+
+        remove_file(&filename).unwrap();
+    }
+}
+
+mod write_opb {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file};
+    use crate::constraint::Constraint;
+
+    #[test]
+    fn it_writes_the_clauses_and_constraints_as_opb() {
+        let mut subject = Subject::new();
+
+        let a = subject.new_variable();
+        let b = subject.new_variable();
+        let mut clause = Clause::new();
+
+        clause.add(Literal::negative(a));
+        subject.add_clause(clause);
+        subject.add_comment("config foo=true".to_string());
+
+        let filename = temp_dir().join("supersat-formula-write-opb.opb");
+        let filename = filename.to_str().unwrap().to_string();
+
+        let at_most_one = Constraint::at_most(&[Literal::positive(a), Literal::positive(b)], 1);
+        subject.write_opb(&filename, &[at_most_one]);
+
+        assert_eq!(read_to_string(&filename).unwrap(), [
+            "* #variable= 2 #constraint= 2",
+            "* config foo=true",
+            "+1 ~x1 >= 1 ;",
+            "+1 ~x1 +1 ~x2 >= 1 ;",
+            "",
+        ].join("\n"));
+
+        remove_file(&filename).unwrap();
+    }
+}
+
 mod display {
     use super::*;
 
@@ -0,0 +1,32 @@
+use std::cmp::min;
+
+use crate::search_strategy::SearchStrategy;
+use crate::binary_search::BinarySearch;
+
+// Steps up from the lower bound by 1, 2, 4, ... permutations until a query is
+// unsatisfiable, then binary searches the last step. It needs few queries when
+// the row is close to the lower bound.
+pub struct Galloping;
+
+impl SearchStrategy for Galloping {
+    fn search(&self, lower: usize, upper: usize, satisfiable: &mut dyn FnMut(usize) -> bool) -> usize {
+        let mut lower = lower;
+        let mut step = 1;
+
+        while lower < upper {
+            let permutations = min(lower + step, upper);
+
+            if !satisfiable(permutations) {
+                return BinarySearch.search(lower, permutations - 1, satisfiable);
+            }
+
+            lower = permutations;
+            step *= 2;
+        }
+
+        lower
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,23 @@
+use super::*;
+use crate::search_strategy::test;
+
+type Subject = Galloping;
+
+fn search(lower: usize, upper: usize, answer: usize) -> (usize, Vec<usize>) {
+    test::search(&Subject {}, lower, upper, answer)
+}
+
+mod search {
+    use super::*;
+
+    #[test]
+    fn it_doubles_its_step_until_a_query_is_unsatisfiable() {
+        assert_eq!(search(20, 35, 35), (35, vec![21, 23, 27, 35]));
+        assert_eq!(search(20, 35, 20), (20, vec![21]));
+    }
+
+    #[test]
+    fn it_binary_searches_the_last_step() {
+        assert_eq!(search(20, 35, 30), (30, vec![21, 23, 27, 35, 31, 29, 30]));
+    }
+}
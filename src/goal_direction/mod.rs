@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter, Result};
+
+// Which way the machine's final states and the goal's states imply each other.
+// The paper's encoding is backward, which lets a permutation appear again
+// without the goal noticing, so wasted symbols can be undercounted with it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GoalDirection {
+    Forward,
+    Backward,
+    Both,
+}
+
+impl Display for GoalDirection {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Forward => write!(f, "forward"),
+            Self::Backward => write!(f, "backward"),
+            Self::Both => write!(f, "both"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,14 @@
+use super::*;
+
+type Subject = GoalDirection;
+
+mod display {
+    use super::*;
+
+    #[test]
+    fn it_formats_the_direction_as_it_is_given_on_the_command_line() {
+        assert_eq!(format!("{}", Subject::Forward), "forward");
+        assert_eq!(format!("{}", Subject::Backward), "backward");
+        assert_eq!(format!("{}", Subject::Both), "both");
+    }
+}
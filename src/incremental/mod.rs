@@ -0,0 +1,64 @@
+use crate::literal::Literal;
+use crate::formula::Formula;
+use crate::machine::Machine;
+use crate::goal::Goal;
+use crate::bounds::Bounds;
+use crate::logic::Logic;
+use crate::problem::Problem;
+use crate::problem_config::ProblemConfig;
+
+// Builds the formula for a length of string once and adds the counter for each
+// query behind an activation literal so the same formula can answer them all.
+pub struct Incremental {
+    n: usize,
+    length_of_string: usize,
+    formula: Formula,
+    machine: Machine,
+    goal: Goal,
+    config: ProblemConfig,
+}
+
+impl Incremental {
+    pub fn new<F: Fn(&mut Problem)>(n: usize, length_of_string: usize, callback: F) -> Self {
+        let mut formula = Formula::new();
+        let machine = Machine::new(n, length_of_string, &mut formula);
+        let goal = Goal::new(n, length_of_string, &mut formula);
+        let bounds = Bounds::new(n, length_of_string, &[]);
+
+        {
+            let mut logic = Logic::new(&mut formula);
+            let mut problem = Problem::new(n, length_of_string, &machine, &goal, &bounds, &mut logic);
+
+            callback(&mut problem);
+        }
+
+        Self { n, length_of_string, formula, machine, goal, config: ProblemConfig::default() }
+    }
+
+    // The counters for each query are encoded with the same config as the
+    // rest of the formula.
+    pub fn set_config(&mut self, config: ProblemConfig) {
+        self.config = config;
+    }
+
+    pub fn query(&mut self, max_permutations: &[usize]) -> Literal {
+        let (n, length_of_string) = (self.n, self.length_of_string);
+        let bounds = Bounds::new(n, length_of_string, max_permutations);
+
+        let mut logic = Logic::new(&mut self.formula);
+        let activation = logic.new_literal();
+
+        let mut problem = Problem::new(n, length_of_string, &self.machine, &self.goal, &bounds, &mut logic);
+        problem.set_config(self.config);
+        problem.the_number_of_wasted_symbols_is_within_bounds_when(activation);
+
+        activation
+    }
+
+    pub fn formula(&self) -> &Formula {
+        &self.formula
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,55 @@
+use super::*;
+
+type Subject = Incremental;
+
+const N: usize = 2;
+const LENGTH: usize = 3;
+
+fn dimacs(formula: &Formula) -> Vec<String> {
+    let mut strings = format!("{}", formula).lines().skip(1)
+        .map(String::from).collect::<Vec<_>>();
+
+    strings.sort();
+
+    strings
+}
+
+mod new {
+    use super::*;
+
+    #[test]
+    fn it_builds_the_formula_with_the_callback() {
+        let subject = Subject::new(N, LENGTH, |problem| {
+            problem.the_machine_starts_in_the_dead_states();
+        });
+
+        assert_eq!(dimacs(subject.formula()), &["-2 0", "-3 0"]);
+    }
+}
+
+mod query {
+    use super::*;
+
+    #[test]
+    fn it_returns_a_new_activation_literal_for_each_query() {
+        let mut subject = Subject::new(N, LENGTH, |_| {});
+
+        let first = subject.query(&[1]);
+        let second = subject.query(&[1]);
+
+        // The machine and goal use the first 13 variables.
+        assert_eq!(first.to_string(), "14");
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn it_guards_every_clause_for_the_query_by_its_activation_literal() {
+        let mut subject = Subject::new(N, LENGTH, |_| {});
+        let activation = subject.query(&[1]).negate().to_string();
+
+        let clauses = dimacs(subject.formula());
+
+        assert_eq!(clauses.len(), 10);
+        assert!(clauses.iter().all(|c| c.split(' ').any(|l| l == activation)));
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::scope;
+
+// Runs numbered jobs on a pool of worker threads. Each job answers whether its
+// part of the query is satisfiable and the queue stops handing out jobs once
+// one of them is, since the rest can't change the answer.
+pub struct JobQueue {
+
+}
+
+impl JobQueue {
+    pub fn run<F: Fn(usize) -> bool + Sync>(jobs: usize, workers: usize, job: F) -> Option<usize> {
+        let next = AtomicUsize::new(0);
+        let satisfiable = Mutex::new(None);
+
+        scope(|scope| {
+            for _ in 0..workers.max(1) {
+                scope.spawn(|| loop {
+                    if satisfiable.lock().unwrap().is_some() {
+                        break;
+                    }
+
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+
+                    if index >= jobs {
+                        break;
+                    }
+
+                    if job(index) {
+                        satisfiable.lock().unwrap().get_or_insert(index);
+                    }
+                });
+            }
+        });
+
+        satisfiable.into_inner().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test;
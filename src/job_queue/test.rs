@@ -0,0 +1,43 @@
+use super::*;
+
+type Subject = JobQueue;
+
+mod run {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_job_that_is_satisfiable() {
+        assert_eq!(Subject::run(10, 4, |index| index == 7), Some(7));
+    }
+
+    #[test]
+    fn it_runs_every_job_if_none_are_satisfiable() {
+        let runs = AtomicUsize::new(0);
+
+        let satisfiable = Subject::run(10, 4, |_| {
+            runs.fetch_add(1, Ordering::SeqCst);
+            false
+        });
+
+        assert_eq!(satisfiable, None);
+        assert_eq!(runs.into_inner(), 10);
+    }
+
+    #[test]
+    fn it_stops_handing_out_jobs_once_one_is_satisfiable() {
+        let runs = AtomicUsize::new(0);
+
+        let satisfiable = Subject::run(10, 1, |index| {
+            runs.fetch_add(1, Ordering::SeqCst);
+            index == 2
+        });
+
+        assert_eq!(satisfiable, Some(2));
+        assert_eq!(runs.into_inner(), 3);
+    }
+
+    #[test]
+    fn it_uses_one_worker_when_given_none() {
+        assert_eq!(Subject::run(3, 0, |index| index == 1), Some(1));
+    }
+}
@@ -0,0 +1,70 @@
+// Published values of max_permutations, i.e. the most permutations a string
+// can contain when it wastes each number of symbols, so the search doesn't
+// have to rediscover them.
+pub struct Known {
+    pub n: usize,
+    pub max_permutations: &'static [usize],
+    pub provenance: &'static str,
+}
+
+const TABLES: [Known; 5] = [
+    Known {
+        n: 2,
+        max_permutations: &[2],
+        provenance: "Trivial, the superpermutation is 121.",
+    },
+    Known {
+        n: 3,
+        max_permutations: &[3, 6],
+        provenance: "Exhaustive search, the superpermutation is 123121321.",
+    },
+    Known {
+        n: 4,
+        max_permutations: &[4, 8, 12, 14, 18, 20, 24],
+        provenance: "Exhaustive search, agrees with --oracle for 4 symbols.",
+    },
+    Known {
+        n: 5,
+        max_permutations: &[
+            5, 10, 15, 20, 23, 28, 33, 36, 41, 46, 49, 53, 58, 62, 66, 70, 74,
+            79, 83, 87, 92, 96, 99, 103, 107, 111, 114, 116, 118, 120,
+        ],
+        provenance: "Benjamin Chaffin's exhaustive search (2014), see \
+                     http://www.njohnston.ca/2014/08/all-minimal-superpermutations-on-five-symbols-have-been-found/",
+    },
+    Known {
+        n: 6,
+        max_permutations: &[
+            6, 12, 18, 24, 30, 34, 40, 46, 52, 56, 62, 68, 74, 78, 84, 90, 94,
+            100, 106, 112, 116, 122, 128, 134, 138, 144, 150, 154, 160, 166,
+            172, 176, 182, 188, 192, 198, 203, 209, 214, 220, 225, 230, 236,
+            241, 246, 252, 257, 262, 268, 274, 279, 284, 289, 295, 300, 306,
+            311, 316, 322, 327, 332, 338, 344, 349, 354, 360, 364, 370, 375,
+            380, 386, 391, 396, 402, 407, 412, 418, 423, 429, 434, 439, 445,
+            450, 455, 461, 465, 470, 476, 481, 486, 492, 497, 502,
+        ],
+        provenance: "The distributed search with Chaffin's method (Houston, Egan et al. 2019), see \
+                     https://www.gregegan.net/SCIENCE/Superpermutations/Superpermutations.html and \
+                     https://github.com/superpermutators/superperm",
+    },
+];
+
+impl Known {
+    pub fn table(n: usize) -> Option<&'static Self> {
+        TABLES.iter().find(|table| table.n == n)
+    }
+
+    // Returns the first rows of the table, or None if they aren't known.
+    pub fn rows(n: usize, rows: usize) -> Option<&'static [usize]> {
+        let table = Self::table(n)?;
+
+        if rows > table.max_permutations.len() {
+            return None;
+        }
+
+        Some(&table.max_permutations[..rows])
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,70 @@
+use super::*;
+use crate::utility::Utility;
+use crate::bounds::Bounds;
+use crate::oracle::Oracle;
+
+type Subject = Known;
+
+mod table {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_table_for_the_number_of_symbols() {
+        let subject = Subject::table(4).unwrap();
+
+        assert_eq!(subject.n, 4);
+        assert_eq!(subject.max_permutations, &[4, 8, 12, 14, 18, 20, 24]);
+        assert!(!subject.provenance.is_empty());
+    }
+
+    #[test]
+    fn it_returns_none_if_there_is_no_table() {
+        assert!(Subject::table(7).is_none());
+    }
+
+    #[test]
+    fn it_only_contains_valid_tables() {
+        for n in 2..=6 {
+            let subject = Subject::table(n).unwrap();
+            let length_of_string = Utility::factorial(n) + n - 1;
+
+            Bounds::new(n, length_of_string, subject.max_permutations);
+        }
+    }
+
+    #[test]
+    fn it_agrees_with_the_oracle() {
+        for n in 2..=4 {
+            let expected = Oracle::new(n).max_permutations(10);
+
+            assert_eq!(Subject::table(n).unwrap().max_permutations, expected.as_slice());
+        }
+    }
+
+    // The oracle slows down sharply after these rows so the rest of the
+    // published tables are only checked for validity.
+    #[test]
+    fn it_agrees_with_the_oracle_on_the_first_rows_for_five_and_six_symbols() {
+        for (n, rows) in [(5, 26), (6, 36)] {
+            let expected = Oracle::new(n).max_permutations(rows);
+
+            assert_eq!(Subject::rows(n, rows).unwrap(), expected.as_slice());
+        }
+    }
+}
+
+mod rows {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_first_rows_of_the_table() {
+        assert_eq!(Subject::rows(5, 3), Some(&[5, 10, 15][..]));
+        assert_eq!(Subject::rows(5, 0), Some(&[][..]));
+    }
+
+    #[test]
+    fn it_returns_none_if_the_rows_arent_known() {
+        assert_eq!(Subject::rows(3, 3), None);
+        assert_eq!(Subject::rows(7, 1), None);
+    }
+}
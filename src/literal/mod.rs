@@ -20,6 +20,12 @@ impl Literal {
         Self::new(variable, false)
     }
 
+    pub fn from_dimacs(number: isize) -> Self {
+        let variable = Variable::new(number.abs() as usize);
+
+        Self::new(variable, number > 0)
+    }
+
     pub fn negate(&self) -> Self {
         Self::new(self.variable, !self.positive)
     }
@@ -41,6 +41,23 @@ mod negative {
     }
 }
 
+mod from_dimacs {
+    use super::*;
+
+    #[test]
+    fn it_builds_a_literal_from_a_signed_number() {
+        let subject = Subject::from_dimacs(-123);
+
+        assert_eq!(subject.variable, Variable::new(123));
+        assert!(!subject.positive);
+
+        let subject = Subject::from_dimacs(45);
+
+        assert_eq!(subject.variable, Variable::new(45));
+        assert!(subject.positive);
+    }
+}
+
 mod negate {
     use super::*;
 
@@ -1,17 +1,55 @@
+use std::collections::HashMap;
+
 use crate::literal::Literal;
 use crate::clause::Clause;
 use crate::formula::Formula;
+use crate::expression::Expression;
 
 use crate::bounds::Bounds;
 use crate::counter::Counter;
 
 pub struct Logic<'a> {
     formula: &'a mut Formula,
+    activation: Option<Literal>,
+    gates: HashMap<Expression, (Literal, bool, bool)>,
+    conditions: Option<HashMap<Vec<Literal>, Option<Literal>>>,
+    counter_overflow: bool,
 }
 
 impl<'a> Logic<'a> {
     pub fn new(formula: &'a mut Formula) -> Self {
-        Self { formula }
+        Self { formula, activation: None, gates: HashMap::new(), conditions: None, counter_overflow: true }
+    }
+
+    // Without clause (5) counters only enforce their lower bounds.
+    pub fn set_counter_overflow(&mut self, counter_overflow: bool) {
+        self.counter_overflow = counter_overflow;
+    }
+
+    pub fn comment(&mut self, comment: String) {
+        self.formula.add_comment(comment);
+    }
+
+    // While conditions are shared, a condition of more than one literal that
+    // is used more than once is replaced with a new literal so that it isn't
+    // repeated in every clause. These are cached by the set of literals.
+    pub fn set_share_conditions(&mut self, share_conditions: bool) {
+        self.conditions = share_conditions.then(HashMap::new);
+    }
+
+    // While an activation literal is set, every clause is only enforced when
+    // it is true so that it can be switched on by assuming it. Gates that were
+    // defined under a different activation can't be reused.
+    pub fn set_activation(&mut self, activation: Option<Literal>) {
+        if activation != self.activation {
+            self.gates.clear();
+
+            if let Some(conditions) = &mut self.conditions {
+                conditions.clear();
+            }
+        }
+
+        self.activation = activation;
     }
 
     pub fn tautology(&mut self, literals: &[Literal]) {
@@ -20,7 +58,7 @@ impl<'a> Logic<'a> {
 
             clause.add(*literal);
 
-            self.formula.add_clause(clause);
+            self.add_clause(clause);
         }
     }
 
@@ -31,10 +69,17 @@ impl<'a> Logic<'a> {
             clause.add(literal.negate());
         }
 
-        self.formula.add_clause(clause);
+        self.add_clause(clause);
     }
 
     pub fn implies(&mut self, condition: &[Literal], consequent: &[Literal]) {
+        match self.shared(condition, consequent.len()) {
+            Some(alias) => self.implies_directly(&[alias], consequent),
+            None => self.implies_directly(condition, consequent),
+        }
+    }
+
+    fn implies_directly(&mut self, condition: &[Literal], consequent: &[Literal]) {
         let mut template = Clause::new();
 
         for literal in Self::negate(condition) {
@@ -46,7 +91,7 @@ impl<'a> Logic<'a> {
 
             clause.add(*literal);
 
-            self.formula.add_clause(clause);
+            self.add_clause(clause);
         }
     }
 
@@ -116,7 +161,7 @@ impl<'a> Logic<'a> {
             // (5) asserts that there can’t be an overflow on any register as it
             // would indicate that more than k variables are true
             let k = current_register.end();
-            if let Some(overflow) = previous_register.literal_for_count(k) {
+            if let Some(overflow) = previous_register.literal_for_count(k).filter(|_| self.counter_overflow) {
                 // I think there's a missing negation in the paper:
                 self.implies(&[literal], &[overflow.negate()]);
             }
@@ -124,8 +169,7 @@ impl<'a> Logic<'a> {
     }
 
     pub fn alias(&mut self, literals: &[Literal]) -> Literal {
-        let variable = self.formula.new_variable();
-        let literal = Literal::positive(variable);
+        let literal = self.new_literal();
 
         self.implies(literals, &[literal]);
         self.implies(&[literal], literals);
@@ -133,6 +177,156 @@ impl<'a> Logic<'a> {
         literal
     }
 
+    // A condition is seen once before it's aliased unless it already has more
+    // than one consequent. The alias only ever appears negated in clauses so
+    // it's enough that the condition implies it, which saves the clauses for
+    // the other direction.
+    fn shared(&mut self, condition: &[Literal], uses: usize) -> Option<Literal> {
+        // A contradiction has no consequents to share the condition between.
+        if condition.len() < 2 || uses == 0 {
+            return None;
+        }
+
+        let mut key = condition.to_vec();
+        key.sort_unstable();
+        key.dedup();
+
+        let seen = self.conditions.as_ref()?.get(&key).cloned();
+
+        let alias = match seen {
+            Some(Some(alias)) => alias,
+            None if uses < 2 => {
+                self.conditions.as_mut()?.insert(key, None);
+                return None;
+            },
+            _ => {
+                let alias = self.new_literal();
+                self.implies_directly(&key, &[alias]);
+                alias
+            },
+        };
+
+        self.conditions.as_mut()?.insert(key, Some(alias));
+        Some(alias)
+    }
+
+    // Conjunctions and disjunctions at the top are added as clauses directly.
+    // Everything below them is only encoded in the direction it's needed in
+    // (Plaisted-Greenbaum).
+    pub fn assert(&mut self, expression: &Expression) {
+        match expression {
+            Expression::And(operands) => {
+                for operand in operands {
+                    self.assert(operand);
+                }
+            },
+            Expression::Or(operands) => {
+                let literals = operands.iter().map(|o| self.encode(o, true, false)).collect::<Vec<_>>();
+                self.contradiction(&Self::negate(&literals));
+            },
+            expression => {
+                let literal = self.encode(expression, true, false);
+                self.tautology(&[literal]);
+            },
+        }
+    }
+
+    // Returns a literal that is equivalent to the expression (Tseitin) so it
+    // can be used in both directions or assumed.
+    pub fn define(&mut self, expression: &Expression) -> Literal {
+        self.encode(expression, true, true)
+    }
+
+    // The literal for each gate is remembered along with which directions have
+    // been added so far: 'positive' if the literal implies the gate and
+    // 'negative' if the gate implies the literal.
+    fn encode(&mut self, expression: &Expression, positive: bool, negative: bool) -> Literal {
+        match expression {
+            Expression::Literal(literal) => return *literal,
+            Expression::Not(expression) => return self.encode(expression, negative, positive).negate(),
+            _ => {},
+        }
+
+        let (gate, has_positive, has_negative) = match self.gates.get(expression) {
+            Some(&entry) => entry,
+            None => (self.new_literal(), false, false),
+        };
+
+        let positive = positive && !has_positive;
+        let negative = negative && !has_negative;
+
+        self.gates.insert(expression.clone(), (gate, has_positive || positive, has_negative || negative));
+
+        if !positive && !negative {
+            return gate;
+        }
+
+        match expression {
+            Expression::And(operands) => {
+                let literals = operands.iter().map(|o| self.encode(o, positive, negative)).collect::<Vec<_>>();
+
+                if positive {
+                    self.implies(&[gate], &literals);
+                }
+
+                if negative {
+                    self.implies(&literals, &[gate]);
+                }
+            },
+            Expression::Or(operands) => {
+                let literals = operands.iter().map(|o| self.encode(o, positive, negative)).collect::<Vec<_>>();
+
+                if positive {
+                    self.contradiction(&Self::and(&[gate], &Self::negate(&literals)));
+                }
+
+                if negative {
+                    for literal in literals {
+                        self.implies(&[literal], &[gate]);
+                    }
+                }
+            },
+            Expression::Xor(a, b) => {
+                let a = self.encode(a, true, true);
+                let b = self.encode(b, true, true);
+
+                if positive {
+                    self.contradiction(&[gate, a, b]);
+                    self.contradiction(&[gate, a.negate(), b.negate()]);
+                }
+
+                if negative {
+                    self.implies(&[a, b.negate()], &[gate]);
+                    self.implies(&[a.negate(), b], &[gate]);
+                }
+            },
+            Expression::Ite(condition, then, otherwise) => {
+                let condition = self.encode(condition, true, true);
+                let then = self.encode(then, positive, negative);
+                let otherwise = self.encode(otherwise, positive, negative);
+
+                if positive {
+                    self.implies(&[gate, condition], &[then]);
+                    self.implies(&[gate, condition.negate()], &[otherwise]);
+                }
+
+                if negative {
+                    self.implies(&[condition, then], &[gate]);
+                    self.implies(&[condition.negate(), otherwise], &[gate]);
+                }
+            },
+            Expression::Literal(_) | Expression::Not(_) => unreachable!(),
+        }
+
+        gate
+    }
+
+    pub fn new_literal(&mut self) -> Literal {
+        let variable = self.formula.new_variable();
+
+        Literal::positive(variable)
+    }
+
     pub fn and(a: &[Literal], b: &[Literal]) -> Vec<Literal> {
         a.iter().chain(b.iter()).cloned().collect()
     }
@@ -140,6 +334,14 @@ impl<'a> Logic<'a> {
     pub fn negate(literals: &[Literal]) -> Vec<Literal> {
         literals.iter().map(|l| l.negate()).collect()
     }
+
+    fn add_clause(&mut self, mut clause: Clause) {
+        if let Some(activation) = self.activation {
+            clause.add(activation.negate());
+        }
+
+        self.formula.add_clause(clause);
+    }
 }
 
 #[cfg(test)]
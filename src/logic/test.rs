@@ -1,5 +1,6 @@
 use super::*;
 use crate::variable::Variable;
+use crate::expression::Expression;
 
 fn positive(number: usize) -> Literal {
     Literal::positive(Variable::new(number))
@@ -33,6 +34,30 @@ mod new {
     }
 }
 
+mod set_activation {
+    use super::*;
+
+    #[test]
+    fn it_adds_the_negated_activation_literal_to_every_clause() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.set_activation(Some(positive(9)));
+        logic.implies(&[positive(1)], &[positive(2), negative(3)]);
+        logic.tautology(&[positive(4)]);
+
+        logic.set_activation(None);
+        logic.contradiction(&[positive(5)]);
+
+        assert_eq!(dimacs(&formula), &[
+            "-1 -3 -9 0",
+            "-1 2 -9 0",
+            "-5 0",
+            "4 -9 0",
+        ]);
+    }
+}
+
 mod tautology {
     use super::*;
 
@@ -114,6 +139,21 @@ mod alias {
     }
 }
 
+mod new_literal {
+    use super::*;
+
+    #[test]
+    fn it_creates_a_positive_literal_for_a_new_variable() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        assert_eq!(logic.new_literal(), positive(1));
+        assert_eq!(logic.new_literal(), positive(2));
+
+        assert_eq!(dimacs(&formula).len(), 0);
+    }
+}
+
 mod and {
     use super::*;
 
@@ -139,3 +179,267 @@ mod negate {
         assert_eq!(Logic::negate(&literals), expected);
     }
 }
+
+mod assert {
+    use super::*;
+
+    #[test]
+    fn it_adds_clauses_for_the_top_level_directly() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.assert(&Expression::and(vec![
+            Expression::literal(positive(111)),
+            Expression::or(Expression::literals(&[negative(222), positive(333)])),
+        ]));
+
+        assert_eq!(dimacs(&formula), &["-222 333 0", "111 0"]);
+    }
+
+    #[test]
+    fn it_only_encodes_the_gates_in_the_direction_they_are_needed() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.assert(&Expression::or(vec![
+            Expression::and(Expression::literals(&[positive(111), positive(222)])),
+            Expression::and(Expression::literals(&[positive(333), positive(444)])),
+        ]));
+
+        assert_eq!(dimacs(&formula), &[
+            // gate -> operands
+            "-1 111 0",
+            "-1 222 0",
+            "-2 333 0",
+            "-2 444 0",
+
+            // one of the gates is true
+            "1 2 0",
+        ]);
+    }
+
+    #[test]
+    fn it_adds_an_empty_clause_for_an_empty_disjunction() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.assert(&Expression::or(vec![]));
+
+        assert_eq!(dimacs(&formula), &["0"]);
+    }
+}
+
+mod define {
+    use super::*;
+    use crate::cdcl::Cdcl;
+    use crate::backend::Backend;
+
+    // Checks that the literal is true exactly when the expression is for every
+    // assignment to the inputs.
+    fn assert_defines<F: Fn(&[bool]) -> bool>(inputs: usize, build: fn(&[Literal]) -> Expression, evaluate: F) {
+        let mut formula = Formula::new();
+        let literals = (0..inputs).map(|_| Literal::positive(formula.new_variable())).collect::<Vec<_>>();
+
+        let mut logic = Logic::new(&mut formula);
+        let defined = logic.define(&build(&literals));
+
+        for assignment in 0..(1 << inputs) {
+            let values = (0..inputs).map(|i| assignment & (1 << i) != 0).collect::<Vec<_>>();
+
+            let mut assumptions = literals.iter().zip(&values)
+                .map(|(literal, value)| if *value { *literal } else { literal.negate() })
+                .collect::<Vec<_>>();
+
+            assumptions.push(defined);
+            let is_true = Cdcl::new().solve(&formula, &assumptions).is_some();
+
+            assumptions.pop();
+            assumptions.push(defined.negate());
+            let is_false = Cdcl::new().solve(&formula, &assumptions).is_some();
+
+            assert_eq!((is_true, is_false), (evaluate(&values), !evaluate(&values)));
+        }
+    }
+
+    #[test]
+    fn it_defines_conjunctions_and_disjunctions() {
+        assert_defines(3, |l| Expression::and(Expression::literals(l)), |v| v.iter().all(|v| *v));
+        assert_defines(3, |l| Expression::or(Expression::literals(l)), |v| v.iter().any(|v| *v));
+    }
+
+    #[test]
+    fn it_defines_xor_and_iff() {
+        assert_defines(2, |l| Expression::xor(Expression::literal(l[0]), Expression::literal(l[1])), |v| v[0] != v[1]);
+        assert_defines(2, |l| Expression::iff(Expression::literal(l[0]), Expression::literal(l[1])), |v| v[0] == v[1]);
+    }
+
+    #[test]
+    fn it_defines_if_then_else() {
+        assert_defines(3, |l| {
+            Expression::ite(Expression::literal(l[0]), Expression::literal(l[1]), Expression::literal(l[2]))
+        }, |v| if v[0] { v[1] } else { v[2] });
+    }
+
+    #[test]
+    fn it_defines_the_equality_of_bit_vectors() {
+        assert_defines(4, |l| Expression::equal(&l[..2], &l[2..]), |v| v[..2] == v[2..]);
+    }
+
+    #[test]
+    fn it_defines_nested_expressions() {
+        assert_defines(4, |l| {
+            let either = Expression::or(vec![Expression::literal(l[0]), Expression::not(Expression::literal(l[1]))]);
+            let both = Expression::and(Expression::literals(&l[2..]));
+
+            Expression::ite(Expression::xor(either, both.clone()), both, Expression::literal(l[0]))
+        }, |v| {
+            let either = v[0] || !v[1];
+            let both = v[2] && v[3];
+
+            if either != both { both } else { v[0] }
+        });
+    }
+
+    #[test]
+    fn it_reuses_the_literal_for_an_expression_that_is_built_again() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        let a = logic.define(&Expression::and(Expression::literals(&[positive(111), negative(222)])));
+        let b = logic.define(&Expression::and(Expression::literals(&[negative(222), positive(111)])));
+        let c = logic.define(&Expression::not(Expression::and(Expression::literals(&[positive(111), negative(222)]))));
+
+        assert_eq!(a, b);
+        assert_eq!(c, a.negate());
+
+        assert_eq!(dimacs(&formula), &["-1 -222 0", "-1 111 0", "1 -111 222 0"]);
+    }
+
+    #[test]
+    fn it_only_adds_the_missing_direction_when_a_gate_is_reused() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        let gate = Expression::or(Expression::literals(&[positive(111), positive(222)]));
+
+        logic.assert(&Expression::or(vec![
+            Expression::and(vec![gate.clone(), Expression::literal(positive(333))]),
+            Expression::literal(positive(444)),
+        ]));
+
+        let literal = logic.define(&gate);
+
+        assert_eq!(literal, positive(2));
+
+        assert_eq!(dimacs(&formula), &[
+            // the conjunction's gate implies the disjunction's gate
+            "-1 2 0",
+            "-1 333 0",
+
+            // the disjunction's gate implies the disjunction
+            "-2 111 222 0",
+            "1 444 0",
+
+            // the disjunction implies its gate once it's defined
+            "2 -111 0",
+            "2 -222 0",
+        ]);
+    }
+
+    #[test]
+    fn it_forgets_the_gates_when_the_activation_changes() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        let gate = Expression::and(Expression::literals(&[positive(111), positive(222)]));
+
+        logic.set_activation(Some(positive(999)));
+        let a = logic.define(&gate);
+
+        logic.set_activation(None);
+        let b = logic.define(&gate);
+
+        assert_ne!(a, b);
+    }
+}
+
+mod set_share_conditions {
+    use super::*;
+
+    #[test]
+    fn it_replaces_a_condition_with_several_consequents_with_a_new_literal() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.set_share_conditions(true);
+        logic.implies(&[positive(111), negative(222)], &[positive(333), positive(444)]);
+        logic.implies(&[negative(222), positive(111)], &[positive(555)]);
+
+        assert_eq!(dimacs(&formula), &[
+            // the new literal implies each consequent
+            "-1 333 0",
+            "-1 444 0",
+            "-1 555 0",
+
+            // and the condition implies it
+            "1 -111 222 0",
+        ]);
+    }
+
+    #[test]
+    fn it_only_replaces_a_condition_with_one_consequent_once_it_is_used_again() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.set_share_conditions(true);
+        logic.implies(&[positive(111), positive(222)], &[positive(333)]);
+        logic.implies(&[positive(111), positive(222)], &[positive(444)]);
+
+        assert_eq!(dimacs(&formula), &["-1 444 0", "-111 -222 333 0", "1 -111 -222 0"]);
+    }
+
+    #[test]
+    fn it_does_not_replace_a_condition_without_consequents() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.set_share_conditions(true);
+        logic.implies(&[positive(111), positive(222)], &[positive(333)]);
+        logic.implies(&[positive(111), positive(222)], &[]);
+
+        assert_eq!(dimacs(&formula), &["-111 -222 333 0"]);
+    }
+
+    #[test]
+    fn it_does_not_replace_conditions_of_a_single_literal() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.set_share_conditions(true);
+        logic.implies(&[positive(111)], &[positive(222), positive(333)]);
+
+        assert_eq!(dimacs(&formula), &["-111 222 0", "-111 333 0"]);
+    }
+
+    #[test]
+    fn it_does_not_reuse_literals_from_a_different_activation() {
+        let mut formula = Formula::new();
+        let mut logic = Logic::new(&mut formula);
+
+        logic.set_share_conditions(true);
+        logic.set_activation(Some(positive(999)));
+        logic.implies(&[positive(111), positive(222)], &[positive(333), positive(444)]);
+
+        logic.set_activation(None);
+        logic.implies(&[positive(111), positive(222)], &[positive(555), positive(666)]);
+
+        assert_eq!(dimacs(&formula), &[
+            "-1 333 -999 0",
+            "-1 444 -999 0",
+            "-2 555 0",
+            "-2 666 0",
+            "1 -111 -222 -999 0",
+            "2 -111 -222 0",
+        ]);
+    }
+}
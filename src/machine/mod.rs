@@ -3,6 +3,7 @@ use std::ops::Range;
 use crate::variable::Variable;
 use crate::formula::Formula;
 use crate::snapshot::Snapshot;
+use crate::model::Model;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Machine {
@@ -21,6 +22,14 @@ impl Machine {
         &self.snapshots[point_in_time]
     }
 
+    pub fn symbol_variables(&self) -> Vec<Variable> {
+        self.snapshots.iter().flat_map(|s| s.symbol_variables().clone()).collect()
+    }
+
+    pub fn decode(&self, model: &Model) -> Vec<usize> {
+        self.snapshots.iter().map(|s| s.symbol(model)).collect()
+    }
+
     pub fn invalid_ranges(&self) -> Vec<(Range<usize>, &Vec<Variable>)> {
         self.snapshots.iter().flat_map(|s| s.invalid_ranges()).collect()
     }
@@ -44,6 +44,42 @@ mod at_time {
     }
 }
 
+mod symbol_variables {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_variables_for_the_symbols_at_each_point_in_time() {
+        let mut formula = Formula::new();
+        let subject = Subject::new(N, 3, &mut formula);
+
+        let numbers = subject.symbol_variables().iter()
+            .map(|v| v.number).collect::<Vec<_>>();
+
+        // Each snapshot has 8 variables and the first 2 are for its symbol.
+        assert_eq!(numbers, &[1, 2, 9, 10, 17, 18]);
+    }
+}
+
+mod decode {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_string_of_symbols_for_the_model() {
+        let mut formula = Formula::new();
+        let subject = Subject::new(N, 3, &mut formula);
+
+        let literals = [
+            subject.at_time(0).state(&[2]).literals().clone(),
+            subject.at_time(1).state(&[3]).literals().clone(),
+            subject.at_time(2).state(&[1]).literals().clone(),
+        ].concat();
+
+        let model = Model::new(&literals);
+
+        assert_eq!(subject.decode(&model), &[2, 3, 1]);
+    }
+}
+
 mod invalid_ranges {
     use super::*;
 
@@ -8,17 +8,58 @@ mod rank;
 mod snapshot;
 mod machine;
 mod bounds;
+mod bounds_error;
 mod register;
 mod counter;
 mod goal;
 mod logic;
+mod expression;
+mod goal_direction;
+mod problem_config;
 mod problem;
 mod solver;
 mod utility;
+mod options;
+mod model;
+mod repair;
+mod enumeration;
+mod model_counter;
+mod backend;
+mod external;
+mod incremental;
+mod cdcl;
+mod oracle;
+mod chaffin;
+mod known;
+mod constraint;
+mod max_sat;
+mod search_strategy;
+mod descending;
+mod binary_search;
+mod galloping;
+mod portfolio;
+mod cubes;
+mod job_queue;
+mod proof;
+mod proof_error;
+mod drat_checker;
+mod dimacs_error;
+mod compression;
+mod preprocessor;
 
-use std::fs::create_dir_all;
+#[cfg(test)]
+mod test_formulas;
+
+use std::fs::{create_dir_all, remove_file};
 use std::cmp::min;
+use std::env::args;
+use std::collections::HashMap;
+use std::time::Instant;
+use std::path::Path;
+use std::thread::available_parallelism;
 
+use crate::variable::Variable;
+use crate::literal::Literal;
 use crate::formula::Formula;
 use crate::machine::Machine;
 use crate::goal::Goal;
@@ -27,65 +68,770 @@ use crate::logic::Logic;
 use crate::problem::Problem;
 use crate::solver::Solver;
 use crate::utility::Utility;
+use crate::options::Options;
+use crate::repair::Repair;
+use crate::enumeration::Enumeration;
+use crate::model_counter::ModelCounter;
+use crate::backend::Backend;
+use crate::external::External;
+use crate::incremental::Incremental;
+use crate::cdcl::Cdcl;
+use crate::oracle::Oracle;
+use crate::chaffin::Chaffin;
+use crate::known::Known;
+use crate::max_sat::MaxSat;
+use crate::search_strategy::SearchStrategy;
+use crate::descending::Descending;
+use crate::binary_search::BinarySearch;
+use crate::galloping::Galloping;
+use crate::portfolio::Portfolio;
+use crate::cubes::Cubes;
+use crate::job_queue::JobQueue;
+use crate::proof::Proof;
+use crate::drat_checker::DratChecker;
+use crate::compression::Compression;
+use crate::preprocessor::Preprocessor;
+use crate::problem_config::ProblemConfig;
 
 fn main() {
     create_dir_all("scratch").unwrap();
 
-    for n in 2..=6 {
+    let options = Options::parse(&args().skip(1).collect::<Vec<_>>());
+
+    if options.is_constrained() && options.symbols.is_none() {
+        panic!("Constraining the string requires --symbols");
+    }
+
+    // Strings are only removed in favour of their reversal, which might not
+    // meet the constraints.
+    if options.break_symmetry && options.is_constrained() {
+        panic!("The reversal of a constrained string might not meet the constraints so --break-symmetry can't be combined with them");
+    }
+
+    if let Some(n) = options.symbols {
+        options.validate(n);
+    }
+
+    // Relaxed encodings can give wrong answers so they're only used to compare
+    // the sizes and solve times of rows that are already known.
+    let config = options.config();
+    let relaxed = !config.is_sound();
+
+    let answers = !options.repair.is_empty() || options.enumerate.is_some() || options.count.is_some() ||
+        options.maxsat.is_some() || options.is_constrained();
+
+    if relaxed && answers {
+        panic!("--goal-direction backward and --no-counter-overflow can give wrong answers so they only work for known rows of max permutations");
+    }
+
+    if !options.repair.is_empty() {
+        return repair(&options);
+    }
+
+    // Enumerating and counting cover every string that starts with ascending
+    // numbers, which the constraints would change.
+    let enumerates = options.enumerate.is_some() || options.count.is_some();
+
+    if enumerates && (options.is_constrained() || options.break_symmetry) {
+        panic!("Enumerating and counting cover every string so they can't be combined with constraints or --break-symmetry");
+    }
+
+    if options.enumerate.is_some() {
+        return enumerate(&options);
+    }
+
+    if options.count.is_some() {
+        return count(&options);
+    }
+
+    if options.oracle {
+        return oracle(&options);
+    }
+
+    if options.maxsat.is_some() {
+        return max_sat(&options);
+    }
+
+    if let Some(filename) = &options.solve {
+        return solve(filename);
+    }
+
+    if options.native_rows.is_some() && options.is_constrained() {
+        panic!("The native engine doesn't support constraining the string");
+    }
+
+    if options.cubes.is_some() && !options.prefix.is_empty() {
+        panic!("Cubes follow the ascending numbers so can't be combined with --prefix");
+    }
+
+    // The incremental formula is built once for every query of its length.
+    if options.incremental && (options.preprocess || options.share_conditions) {
+        panic!("--incremental builds its formula once per length so it can't be combined with --preprocess or --share-conditions");
+    }
+
+    // The proof would only show the preprocessed formula is unsatisfiable.
+    if options.proof && options.preprocess {
+        panic!("Proofs are for the original formula so --proof can't be combined with --preprocess");
+    }
+
+    // Only a single solver on the whole query writes a proof, so the answers on
+    // the other paths would become rows without one.
+    let unproven = [
+        ("--portfolio", !options.portfolio.is_empty()),
+        ("--cubes", options.cubes.is_some()),
+        ("--incremental", options.incremental),
+        ("--pb", options.pb.is_some()),
+        ("--native-rows", options.native_rows.is_some()),
+    ];
+
+    if let (true, Some((name, _))) = (options.proof, unproven.iter().find(|(_, used)| *used)) {
+        panic!("Proofs are only written for whole queries so --proof can't be combined with {}", name);
+    }
+
+    let variant = options.variant();
+    let extension = extension(&options);
+    let (name, strategy) = strategy(&options);
+    let symbols = options.symbols.map_or(2..=6, |n| n..=n);
+
+    for n in symbols {
         println!("------------------------------------------------------------");
         println!("Trying to find superpermutations for {} symbols.", n);
         println!("------------------------------------------------------------");
         println!();
 
-        let mut max_permutations = vec![];
+        let mut max_permutations = known_rows(n, &options);
+        let mut sessions = HashMap::new();
+        let mut satisfiable_calls = 0;
+        let mut unsatisfiable_calls = 0;
 
-        let mut permutations = max_permutations.last().unwrap_or(&0) + n;
-        let mut wasted_symbols;
-        let mut length_of_string;
+        let mut query = |permutations: usize, max_permutations: &[usize]| {
+            let wasted_symbols = n - 1 + max_permutations.len();
+            let length_of_string = permutations + wasted_symbols;
 
-        loop {
-            wasted_symbols = n - 1 + max_permutations.len();
-            length_of_string = permutations + wasted_symbols;
+            print!("Searching for a string of length {} that contains ", length_of_string);
+            println!("{} permutations and wastes {} symbols...", permutations, wasted_symbols);
 
-            let filename = format!("scratch/{}-symbols-{}-perms-{}-waste.dimacs", n, permutations, wasted_symbols);
+            let started = Instant::now();
 
-            Formula::generate(&filename, |formula| {
-                println!("Generating {}...", filename);
+            let bounds = Bounds::new(n, length_of_string, max_permutations);
 
-                let machine = Machine::new(n, length_of_string, formula);
-                let goal = Goal::new(n, length_of_string, formula);
-                let bounds = Bounds::new(n, length_of_string, &max_permutations);
-                let mut logic = Logic::new(formula);
-                let mut problem = Problem::new(n, length_of_string, &machine, &goal, &bounds, &mut logic);
+            let satisfiable = if let Err(error) = bounds.diagnose() {
+                println!("{}.", error);
+                false
+            } else if max_permutations.len() < options.native_rows.unwrap_or(0) {
+                Chaffin::new(n, max_permutations).search(permutations).is_some()
+            } else if options.incremental {
+                let filename = format!("scratch/{}-symbols-{}-length{}.icnf", n, length_of_string, variant);
 
-                problem.the_machine_starts_in_the_dead_states();
-                problem.the_machine_changes_state_when_it_reads_input();
-                problem.each_permutation_appears_at_most_once();
-                problem.the_string_starts_with_ascending_numbers();
-                problem.all_binary_representations_map_to_states();
-                problem.the_number_of_wasted_symbols_is_within_bounds();
-            });
+                let (session, backend) = sessions.entry(length_of_string).or_insert_with(|| {
+                    println!("Generating {}...", filename);
 
-            print!("Searching for a string of length {} that contains ", length_of_string);
-            println!("{} permutations and wastes {} symbols...", permutations, wasted_symbols);
+                    let mut session = Incremental::new(n, length_of_string, |problem| {
+                        problem.set_config(config);
+                        constrain(problem, &options);
+                    });
+
+                    session.set_config(config);
+
+                    let backend: Box<dyn Backend> = match options.builtin {
+                        true => Box::new(Cdcl::new()),
+                        false => Box::new(External::new("ilingeling", &filename)),
+                    };
+
+                    (session, backend)
+                });
+
+                let activation = session.query(max_permutations);
+
+                backend.solve(session.formula(), &[activation]).is_some()
+            } else {
+                let filename = format!("scratch/{}-symbols-{}-perms-{}-waste{}.dimacs{}", n, permutations, wasted_symbols, variant, extension);
+
+                // Pseudo-Boolean solvers are given the bounds as constraints
+                // instead of the counters.
+                let encode = |formula: &mut Formula, pseudo_boolean: bool, config: ProblemConfig, share_conditions: bool| {
+                    let machine = Machine::new(n, length_of_string, formula);
+                    let goal = Goal::new(n, length_of_string, formula);
+                    let bounds = Bounds::new(n, length_of_string, max_permutations);
+                    let mut logic = Logic::new(formula);
+                    logic.set_share_conditions(share_conditions);
+
+                    let mut problem = Problem::new(n, length_of_string, &machine, &goal, &bounds, &mut logic);
+
+                    problem.set_config(config);
+                    constrain(&mut problem, &options);
+
+                    if pseudo_boolean {
+                        let constraints = problem.constraints_for_wasted_symbols();
+                        return (machine, constraints);
+                    }
+
+                    problem.the_number_of_wasted_symbols_is_within_bounds();
+
+                    (machine, vec![])
+                };
+
+                // The formula is encoded again without sharing to measure it.
+                let build = |formula: &mut Formula, pseudo_boolean: bool, config: ProblemConfig| {
+                    let encoded = encode(formula, pseudo_boolean, config, options.share_conditions);
+
+                    if options.share_conditions && !pseudo_boolean {
+                        let mut unshared = Formula::new();
+                        encode(&mut unshared, false, config, false);
 
-            if Solver::solve(&filename) {
-                max_permutations.push(permutations);
-                println!("Setting max permutations to {:?}", max_permutations);
+                        let (clauses_before, literals_before) = size(&unshared);
+                        let (clauses_after, literals_after) = size(formula);
 
-                if permutations == Utility::factorial(n) {
-                    break;
+                        print!("Sharing conditions changed {} clauses ({} literals) ", clauses_before, literals_before);
+                        println!("to {} clauses ({} literals).", clauses_after, literals_after);
+                    }
+
+                    encoded
+                };
+
+                if let Some(depth) = options.cubes {
+                    // Cubes can't fix symbols past the end of the string.
+                    let depth = min(depth, length_of_string - n);
+                    let prefixes = Cubes::prefixes(n, depth);
+
+                    let mut formula = Formula::new();
+                    let (machine, _) = build(&mut formula, false, config);
+
+                    // The cubes assume symbols so those can't be simplified away.
+                    if options.preprocess {
+                        formula = preprocess(&formula, &machine.symbol_variables()).formula();
+                    }
+
+                    let cubes = prefixes.iter().map(|prefix| Cubes::literals(n, &machine, prefix)).collect::<Vec<_>>();
+                    let jobs = options.jobs.unwrap_or_else(|| available_parallelism().map_or(1, |j| j.get()));
+
+                    println!("Conquering {} cubes with {} jobs...", cubes.len(), jobs);
+
+                    let satisfiable = JobQueue::run(cubes.len(), jobs, |index| {
+                        if options.builtin {
+                            return Cdcl::new().solve(&formula, &cubes[index]).is_some();
+                        }
+
+                        let prefix = prefixes[index].iter().map(|s| s.to_string()).collect::<String>();
+                        let filename = format!("scratch/{}-symbols-{}-perms-{}-waste{}-cube-{}.dimacs{}", n, permutations, wasted_symbols, variant, prefix, extension);
+
+                        if !Path::new(&filename).exists() {
+                            formula.write_cube(&filename, &cubes[index]);
+                        }
+
+                        Solver::solve(&filename)
+                    });
+
+                    if let Some(index) = satisfiable {
+                        println!("The cube {:?} is satisfiable.", prefixes[index]);
+                    }
+
+                    satisfiable.is_some()
+                } else if options.builtin {
+                    let mut formula = Formula::new();
+                    let (machine, _) = build(&mut formula, false, config);
+
+                    let preprocessor = options.preprocess.then(|| preprocess(&formula, &[]));
+
+                    if let Some(preprocessor) = &preprocessor {
+                        formula = preprocessor.formula();
+                    }
+
+                    if options.proof {
+                        let proof = proof_filename(&options, &filename);
+                        let satisfiable = Cdcl::with_proof(&proof).solve(&formula, &[]).is_some();
+
+                        certify(&options, satisfiable, &filename, &proof, || formula);
+                        satisfiable
+                    } else {
+                        let model = Cdcl::new().solve(&formula, &[]);
+
+                        // Eliminated symbols are put back before the string is decoded.
+                        if let (Some(model), Some(preprocessor)) = (&model, &preprocessor) {
+                            let string = machine.decode(&preprocessor.reconstruct(model));
+                            println!("Found {}.", string.iter().map(|s| s.to_string()).collect::<String>());
+                        }
+
+                        model.is_some()
+                    }
+                } else if let Some(command) = &options.pb {
+                    let filename = format!("scratch/{}-symbols-{}-perms-{}-waste{}.opb", n, permutations, wasted_symbols, variant);
+
+                    if !Path::new(&filename).exists() {
+                        println!("Generating {}...", filename);
+
+                        let mut formula = Formula::new();
+                        let (_, constraints) = build(&mut formula, true, config);
+
+                        formula.write_opb(&filename, &constraints);
+                    }
+
+                    Solver::model_with(command, &filename).is_some()
+                } else if !options.portfolio.is_empty() {
+                    let configs = match options.portfolio_encodings {
+                        true => config.alternatives(),
+                        false => vec![config],
+                    };
+
+                    let mut entries = vec![];
+
+                    for config in configs {
+                        let variant = options.variant_with(config);
+                        let filename = format!("scratch/{}-symbols-{}-perms-{}-waste{}.dimacs{}", n, permutations, wasted_symbols, variant, extension);
+
+                        Formula::generate(&filename, |formula| {
+                            println!("Generating {}...", filename);
+                            build(formula, false, config);
+
+                            if options.preprocess {
+                                *formula = preprocess(formula, &[]).formula();
+                            }
+                        });
+
+                        for command in &options.portfolio {
+                            entries.push((command.clone(), filename.clone()));
+                        }
+                    }
+
+                    Portfolio::solve(&entries).is_some()
+                } else {
+                    Formula::generate(&filename, |formula| {
+                        println!("Generating {}...", filename);
+                        build(formula, false, config);
+
+                        if options.preprocess {
+                            *formula = preprocess(formula, &[]).formula();
+                        }
+                    });
+
+                    if options.proof {
+                        let proof = proof_filename(&options, &filename);
+                        let command = options.proof_solver.as_ref().map_or("cadical", |c| c.as_str());
+                        let satisfiable = Solver::model_with_proof(command, &filename, &proof).is_some();
+
+                        certify(&options, satisfiable, &filename, &proof, || Formula::read(&filename));
+
+                        satisfiable
+                    } else {
+                        Solver::solve(&filename)
+                    }
                 }
+            };
 
-                permutations = min(permutations + n, Utility::factorial(n));
+            println!("Took {:.3} seconds.", started.elapsed().as_secs_f64());
+
+            if satisfiable {
+                satisfiable_calls += 1;
             } else {
-                permutations -= 1;
-                println!("None exist, backtracking by one permutation.");
+                unsatisfiable_calls += 1;
+                println!("None exist.");
+            }
+
+            println!();
+
+            satisfiable
+        };
+
+        let all_permutations = Utility::factorial(n);
+
+        if options.is_constrained() {
+            let max_permutations = constrained_rows(n, &options);
+            let length_of_string = all_permutations + n - 1 + max_permutations.len();
+
+            match query(all_permutations, &max_permutations) {
+                true => println!("A superpermutation of length {} meets the constraints.", length_of_string),
+                false => println!("No superpermutation of length {} meets the constraints.", length_of_string),
+            }
+
+            println!();
+            continue;
+        }
+
+        while max_permutations.last() != Some(&all_permutations) {
+            // Each wasted symbol can be followed by at most n new permutations.
+            let lower = max_permutations.last().cloned().unwrap_or(0);
+            let upper = min(lower + n, all_permutations);
+
+            // The table only ever grows with rows from a sound encoding.
+            let known = Known::rows(n, max_permutations.len() + 1).map(|rows| rows[max_permutations.len()]);
+
+            if relaxed && known.is_none() {
+                println!("The relaxed encoding can't extend the known rows of max permutations.");
+                println!();
+                break;
+            }
+
+            let mut row = strategy.search(lower, upper, &mut |permutations| query(permutations, &max_permutations));
+
+            if let (true, Some(known)) = (relaxed, known) {
+                println!("The relaxed encoding found {} permutations where the known row is {}.", row, known);
+                row = known;
             }
 
+            max_permutations.push(row);
+            println!("Setting max permutations to {:?}", max_permutations);
             println!();
         }
 
+        if max_permutations.last() != Some(&all_permutations) {
+            continue;
+        }
+
+        let length_of_string = all_permutations + n - 1 + max_permutations.len() - 1;
+
+        print!("Used the {} strategy, which made {} satisfiable ", name, satisfiable_calls);
+        println!("and {} unsatisfiable calls.", unsatisfiable_calls);
+        println!();
+        println!("The shortest superpermutation for {} symbols is {}.", n, length_of_string);
+        println!();
+    }
+}
+
+// Unsatisfiable answers become permanent rows of max_permutations so their
+// proofs are kept, and checked if there's a checker, before they're accepted.
+// The built-in checker needs the formula, which is only built if it's used.
+fn certify<F: FnOnce() -> Formula>(options: &Options, satisfiable: bool, input: &str, proof: &String, formula: F) {
+    if satisfiable {
+        if Path::new(proof).exists() {
+            remove_file(proof).unwrap();
+        }
+
+        return;
+    }
+
+    let command = match &options.check_proof {
+        Some(command) => command,
+        None => return println!("Kept the proof in {}.", proof),
+    };
+
+    if command == "builtin" {
+        if let Err(error) = DratChecker::new(&formula()).check(proof) {
+            panic!("Failed to verify the proof in {}: {}", proof, error);
+        }
+    } else {
+        // External checkers read the formula from a file and can't read it
+        // compressed.
+        let input = Compression::strip(input);
+
+        if !Path::new(&input).exists() {
+            formula().write(&input);
+        }
+
+        if !Proof::check(command, &input, proof) {
+            panic!("Failed to verify the proof in {}", proof);
+        }
+    }
+
+    println!("Verified the proof in {} with {}.", proof, command);
+}
+
+// Proofs for external checkers, such as drat-trim, aren't compressed since
+// they can't read them.
+fn proof_filename(options: &Options, filename: &str) -> String {
+    let proof = Compression::append(filename, ".drat");
+
+    match options.check_proof.as_deref() {
+        None | Some("builtin") => proof,
+        Some(_) => Compression::strip(&proof),
+    }
+}
+
+fn preprocess(formula: &Formula, frozen: &[Variable]) -> Preprocessor {
+    let mut preprocessor = Preprocessor::new(formula);
+
+    preprocessor.freeze(frozen);
+    preprocessor.simplify();
+
+    let simplified = preprocessor.formula();
+
+    let (clauses_before, literals_before) = size(formula);
+    let (clauses_after, literals_after) = size(&simplified);
+
+    print!("Preprocessing shrank {} clauses ({} literals) ", clauses_before, literals_before);
+    println!("to {} clauses ({} literals).", clauses_after, literals_after);
+
+    preprocessor
+}
+
+fn size(formula: &Formula) -> (usize, usize) {
+    let literals = formula.clauses().iter().map(|c| c.literals().len()).sum::<usize>();
+    (formula.clauses().len(), literals)
+}
+
+// DIMACS files and the outputs and proofs that go with them are compressed
+// when --compress is given.
+fn extension(options: &Options) -> String {
+    match options.compress.as_deref() {
+        None => String::new(),
+        Some(extension @ ("gz" | "xz" | "zst")) => format!(".{}", extension),
+        Some(extension) => panic!("Unknown compression {}", extension),
+    }
+}
+
+fn strategy(options: &Options) -> (&str, Box<dyn SearchStrategy>) {
+    let name = options.strategy.as_ref().map_or("descending", |s| s.as_str());
+
+    let strategy: Box<dyn SearchStrategy> = match name {
+        "descending" => Box::new(Descending),
+        "binary" => Box::new(BinarySearch),
+        "galloping" => Box::new(Galloping),
+        _ => panic!("Unknown strategy {}", name),
+    };
+
+    (name, strategy)
+}
+
+// The last row of a table is n! so the search needs to start before it.
+fn known_rows(n: usize, options: &Options) -> Vec<usize> {
+    let rows = match options.known_rows {
+        Some(rows) => rows,
+        None => return vec![],
+    };
+
+    let max_permutations = match Known::rows(n, rows + 1) {
+        Some(max_permutations) => &max_permutations[..rows],
+        None => panic!("Fewer than {} rows of max permutations are known for {} symbols", rows + 1, n),
+    };
+
+    println!("Starting from the known max permutations {:?}", max_permutations);
+    println!("{}", Known::table(n).unwrap().provenance);
+    println!();
+
+    max_permutations.to_vec()
+}
+
+// Rows found under constraints would only hold for the constrained strings but
+// Bounds applies them to every prefix, which needn't meet the constraints. So
+// a constrained query is a single one, bounded by the published rows for
+// unconstrained strings, and the length defaults to the shortest known.
+fn constrained_rows(n: usize, options: &Options) -> Vec<usize> {
+    let all_permutations = Utility::factorial(n);
+
+    let table = match Known::table(n) {
+        Some(table) => table,
+        None => panic!("No max permutations are known for {} symbols to bound the query", n),
+    };
+
+    let length_of_string = match options.length.or_else(|| shortest_length(n)) {
+        Some(length_of_string) => length_of_string,
+        None => panic!("The shortest superpermutation for {} symbols isn't known so --length is required", n),
+    };
+
+    let wasted_symbols = match length_of_string.checked_sub(all_permutations + n - 1) {
+        Some(wasted_symbols) => wasted_symbols,
+        None => panic!("A string of length {} can't contain all permutations of {} symbols", length_of_string, n),
+    };
+
+    let mut max_permutations = table.max_permutations.iter().take(wasted_symbols).cloned().collect::<Vec<_>>();
+
+    // Past the published rows, each wasted symbol can be followed by at most n
+    // new permutations.
+    while max_permutations.len() < wasted_symbols {
+        let previous = max_permutations.last().cloned().unwrap_or(0);
+        max_permutations.push(min(previous + n, all_permutations));
+    }
+
+    max_permutations
+}
+
+// The known table is complete once its last row reaches every permutation.
+fn shortest_length(n: usize) -> Option<usize> {
+    let all_permutations = Utility::factorial(n);
+    let table = Known::table(n)?;
+
+    match table.max_permutations.last() == Some(&all_permutations) {
+        true => Some(all_permutations + n - 1 + table.max_permutations.len() - 1),
+        false => None,
+    }
+}
+
+fn constrain(problem: &mut Problem, options: &Options) {
+    problem.the_machine_starts_in_the_dead_states();
+    problem.the_machine_changes_state_when_it_reads_input();
+    problem.each_permutation_appears_at_most_once();
+    problem.all_binary_representations_map_to_states();
+
+    // Constraints past the end of the string make the query unsatisfiable.
+    if options.prefix.is_empty() {
+        problem.the_string_starts_with_ascending_numbers();
+    } else {
+        problem.the_string_starts_with(&options.prefix);
+    }
+
+    problem.the_string_ends_with(&options.suffix);
+
+    for &(time, symbol) in &options.fixed {
+        problem.the_string_has_symbol_at(time, symbol);
+    }
+
+    for substring in &options.substrings {
+        problem.the_string_contains(substring);
+    }
+
+    if options.break_symmetry {
+        problem.the_string_precedes_its_reversal();
+    }
+}
+
+fn repair(options: &Options) {
+    let n = *options.repair.iter().max().unwrap();
+    let window = options.window.unwrap_or(2 * n);
+
+    println!("------------------------------------------------------------");
+    println!("Trying to shorten a string of length {} for {} symbols.", options.repair.len(), n);
+    println!("------------------------------------------------------------");
+    println!();
+
+    let mut repair = Repair::new(n, &options.repair);
+    repair.set_config(options.config());
+    repair.improve(window);
+
+    let string = repair.string().iter().map(|s| s.to_string()).collect::<String>();
+
+    println!();
+    println!("The repaired string has length {}:", repair.string().len());
+    println!("{}", string);
+}
+
+fn enumerate(options: &Options) {
+    let n = options.symbols.expect("Enumerating requires --symbols");
+    let length_of_string = options.enumerate.unwrap();
+
+    println!("------------------------------------------------------------");
+    println!("Enumerating strings of length {} that contain all permutations of {} symbols.", length_of_string, n);
+    println!("------------------------------------------------------------");
+    println!();
+
+    let mut enumeration = Enumeration::new(n, length_of_string);
+    enumeration.set_config(options.config());
+    let mut solutions = enumeration.solutions(options.builtin);
+
+    if options.distinct {
+        solutions = enumeration.distinct(&solutions);
+    }
+
+    println!();
+    println!("There are {} strings:", solutions.len());
+
+    for string in &solutions {
+        println!("{}", Enumeration::format(string));
+    }
+}
+
+fn count(options: &Options) {
+    let n = options.symbols.expect("Counting requires --symbols");
+    let length_of_string = options.count.unwrap();
+    let counter = options.counter.as_ref().map_or("ganak", |c| c.as_str());
+
+    let mut enumeration = Enumeration::new(n, length_of_string);
+    enumeration.set_config(options.config());
+    let (formula, machine) = enumeration.formula();
+
+    let filename = format!("scratch/{}-symbols-{}-length-projected.dimacs", n, length_of_string);
+
+    println!("Generating {}...", filename);
+    formula.write_projected(&filename, &machine.symbol_variables());
+
+    println!("Counting strings of length {} with {}...", length_of_string, counter);
+    let count = ModelCounter::count(counter, &filename);
+
+    // Relabelling maps each string to the n! strings that start with some
+    // permutation. The shortest strings all do, otherwise their first symbol
+    // could be dropped, so only then is that every string.
+    let relabelled = count * Utility::factorial(n) as u128;
+
+    println!();
+    println!("There are {} strings that start with ascending numbers.", count);
+    println!("There are {} strings that start with a permutation.", relabelled);
+
+    if shortest_length(n) == Some(length_of_string) {
+        println!("There are {} strings in total.", relabelled);
+    }
+}
+
+fn oracle(options: &Options) {
+    let n = options.symbols.expect("The oracle requires --symbols");
+
+    println!("Searching for the max permutations of {} symbols without SAT...", n);
+
+    let max_permutations = Oracle::new(n).max_permutations(usize::MAX);
+
+    println!("The max permutations are {:?}", max_permutations);
+}
+
+// Solves a DIMACS file with the built-in solver and prints the answer the way
+// external solvers do, so cached formulas can be checked without them.
+fn solve(filename: &String) {
+    let formula = Formula::read(filename);
+
+    match Cdcl::new().solve(&formula, &[]) {
+        Some(model) => {
+            println!("s SATISFIABLE");
+
+            let numbers = (1..=formula.number_of_variables()).map(|number| {
+                let literal = Literal::positive(Variable::new(number));
+                if model.satisfies(&[literal]) { number as isize } else { -(number as isize) }
+            });
+
+            println!("v {} 0", numbers.map(|n| n.to_string()).collect::<Vec<_>>().join(" "));
+        },
+        None => println!("s UNSATISFIABLE"),
+    }
+}
+
+// Asks a MaxSAT solver for the most permutations in a string of each length,
+// rather than searching for them with a series of satisfiability queries.
+fn max_sat(options: &Options) {
+    let command = options.maxsat.as_ref().unwrap();
+    let variant = options.variant();
+    let symbols = options.symbols.map_or(2..=6, |n| n..=n);
+
+    for n in symbols {
+        println!("------------------------------------------------------------");
+        println!("Trying to find superpermutations for {} symbols with {}.", n, command);
+        println!("------------------------------------------------------------");
+        println!();
+
+        let all_permutations = Utility::factorial(n);
+        let mut length_of_string = n;
+
+        loop {
+            let filename = format!("scratch/{}-symbols-{}-length{}.wcnf", n, length_of_string, variant);
+
+            if !Path::new(&filename).exists() {
+                println!("Generating {}...", filename);
+
+                let mut formula = Formula::new();
+                let machine = Machine::new(n, length_of_string, &mut formula);
+                let goal = Goal::new(n, length_of_string, &mut formula);
+                let bounds = Bounds::new(n, length_of_string, &[]);
+                let mut logic = Logic::new(&mut formula);
+                logic.set_share_conditions(options.share_conditions);
+
+                let mut problem = Problem::new(n, length_of_string, &machine, &goal, &bounds, &mut logic);
+
+                problem.set_config(options.config());
+                constrain(&mut problem, options);
+                let soft = problem.literals_for_permutations_appearing();
+
+                formula.write_wcnf(&filename, &soft);
+            }
+
+            let permutations = match MaxSat::cost(command, &filename) {
+                Some(cost) => all_permutations - cost,
+                None => 0,
+            };
+
+            print!("A string of length {} contains at most {} permutations ", length_of_string, permutations);
+            println!("and wastes {} symbols.", length_of_string - permutations);
+
+            if permutations == all_permutations {
+                break;
+            }
+
+            length_of_string += 1;
+        }
+
         println!();
         println!("The shortest superpermutation for {} symbols is {}.", n, length_of_string);
         println!();
@@ -0,0 +1,73 @@
+use std::process::{Command, ExitStatus};
+
+use std::io::{BufRead, BufReader};
+use std::fs::{File, remove_file};
+use std::path::Path;
+
+pub struct MaxSat {
+
+}
+
+impl MaxSat {
+    // Returns the weight of the soft clauses the solver had to falsify, or
+    // None if the hard clauses can't be satisfied.
+    pub fn cost(command: &str, input: &String) -> Option<usize> {
+        let filename = Self::filename(command, input);
+
+        if !Path::new(&filename).exists() {
+            let output = File::create(&filename).unwrap();
+
+            let status = match Command::new(command).arg(input).stdout(output).status() {
+                Ok(status) => status,
+                Err(_) => panic!("Failed to run {} on {}", command, input),
+            };
+
+            // The output of a crash would be read as the answer next time.
+            if !Self::finished(status) {
+                remove_file(&filename).unwrap();
+                panic!("Failed to run {} on {}: {}", command, input, status);
+            }
+        }
+
+        let output = File::open(&filename).unwrap();
+        let lines = BufReader::new(output).lines().map(|l| l.unwrap());
+
+        match Self::parse(lines) {
+            Some(cost) => cost,
+            None => panic!("Failed to parse {}", filename),
+        }
+    }
+
+    // Solvers in the MaxSAT Evaluations exit with 10, 20 or 30 when the hard
+    // clauses are satisfiable, unsatisfiable or the optimum was found.
+    fn finished(status: ExitStatus) -> bool {
+        status.success() || matches!(status.code(), Some(10 | 20 | 30))
+    }
+
+    fn filename(command: &str, input: &str) -> String {
+        let command = Path::new(command).file_name().unwrap().to_str().unwrap();
+
+        format!("{}.{}.output", input, command)
+    }
+
+    // Solvers print 'o <cost>' each time they improve on the best solution so
+    // far, so the optimum is the last one once they report it was found.
+    fn parse<I: Iterator<Item=String>>(lines: I) -> Option<Option<usize>> {
+        let mut cost = None;
+
+        for line in lines {
+            if let Some(number) = line.strip_prefix("o ") {
+                cost = number.trim().parse().ok();
+            } else if line.starts_with("s UNSATISFIABLE") {
+                return Some(None);
+            } else if line.starts_with("s OPTIMUM FOUND") {
+                return cost.map(Some);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,79 @@
+use super::*;
+use std::env::temp_dir;
+use std::fs::{write, set_permissions, Permissions};
+use std::os::unix::fs::PermissionsExt;
+
+type Subject = MaxSat;
+
+fn lines(text: &str) -> impl Iterator<Item=String> + '_ {
+    text.lines().map(String::from)
+}
+
+fn solver(name: &str, script: &str) -> (String, String) {
+    let solver = temp_dir().join(format!("supersat-max-sat-{}.sh", name));
+    let solver = solver.to_str().unwrap().to_string();
+
+    write(&solver, script).unwrap();
+    set_permissions(&solver, Permissions::from_mode(0o755)).unwrap();
+
+    let input = temp_dir().join(format!("supersat-max-sat-{}.wcnf", name));
+    let input = input.to_str().unwrap().to_string();
+
+    (solver, input)
+}
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_last_cost_once_the_optimum_is_found() {
+        let output = "c rc2\no 5\no 3\ns OPTIMUM FOUND\nv -1 2 -3\n";
+
+        assert_eq!(Subject::parse(lines(output)), Some(Some(3)));
+    }
+
+    #[test]
+    fn it_returns_no_cost_if_the_hard_clauses_are_unsatisfiable() {
+        let output = "c rc2\ns UNSATISFIABLE\n";
+
+        assert_eq!(Subject::parse(lines(output)), Some(None));
+    }
+
+    #[test]
+    fn it_returns_none_if_the_optimum_wasnt_found() {
+        let output = "c rc2\no 5\nc interrupted\n";
+
+        assert_eq!(Subject::parse(lines(output)), None);
+    }
+}
+
+mod cost {
+    use super::*;
+
+    #[test]
+    fn it_accepts_the_exit_codes_of_the_maxsat_evaluations() {
+        let (solver, input) = solver("optimum", "#!/bin/sh\necho 'o 2'\necho 's OPTIMUM FOUND'\nexit 30\n");
+
+        assert_eq!(Subject::cost(&solver, &input), Some(2));
+
+        remove_file(Subject::filename(&solver, &input)).unwrap();
+        remove_file(&solver).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to run")]
+    fn it_panics_if_the_solver_fails() {
+        let (solver, input) = solver("crash", "#!/bin/sh\necho 's OPTIMUM FOUND'\nexit 1\n");
+
+        Subject::cost(&solver, &input);
+    }
+}
+
+mod filename {
+    use super::*;
+
+    #[test]
+    fn it_names_the_output_after_the_command_without_its_directory() {
+        assert_eq!(Subject::filename("/opt/rc2.py", "scratch/x.wcnf"), "scratch/x.wcnf.rc2.py.output");
+    }
+}
@@ -0,0 +1,23 @@
+use std::collections::HashSet;
+
+use crate::literal::Literal;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Model {
+    literals: HashSet<Literal>,
+}
+
+impl Model {
+    pub fn new(literals: &[Literal]) -> Self {
+        let literals = literals.iter().cloned().collect();
+
+        Self { literals }
+    }
+
+    pub fn satisfies(&self, literals: &[Literal]) -> bool {
+        literals.iter().all(|l| self.literals.contains(l))
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,31 @@
+use super::*;
+
+type Subject = Model;
+
+mod new {
+    use super::*;
+
+    #[test]
+    fn it_builds_a_model_from_the_true_literals() {
+        let literals = [Literal::from_dimacs(1), Literal::from_dimacs(-2)];
+        let subject = Subject::new(&literals);
+
+        assert_eq!(subject.literals.len(), 2);
+    }
+}
+
+mod satisfies {
+    use super::*;
+
+    #[test]
+    fn it_returns_true_if_all_of_the_literals_are_true_in_the_model() {
+        let literals = [Literal::from_dimacs(1), Literal::from_dimacs(-2), Literal::from_dimacs(3)];
+        let subject = Subject::new(&literals);
+
+        assert!(subject.satisfies(&[Literal::from_dimacs(1), Literal::from_dimacs(-2)]));
+        assert!(subject.satisfies(&[]));
+
+        assert!(!subject.satisfies(&[Literal::from_dimacs(1), Literal::from_dimacs(2)]));
+        assert!(!subject.satisfies(&[Literal::from_dimacs(-3)]));
+    }
+}
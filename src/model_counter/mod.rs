@@ -0,0 +1,69 @@
+use std::process::Command;
+
+use std::io::{BufRead, BufReader};
+use std::fs::{File, remove_file};
+use std::path::Path;
+
+pub struct ModelCounter {
+
+}
+
+impl ModelCounter {
+    pub fn count(command: &str, input: &String) -> u128 {
+        let filename = Self::filename(command, input);
+
+        if !Path::new(&filename).exists() {
+            let output = File::create(&filename).unwrap();
+
+            let status = match Command::new(command).arg(input).stdout(output).status() {
+                Ok(status) => status,
+                Err(_) => panic!("Failed to run {} on {}", command, input),
+            };
+
+            // The output of a crash would be read as the answer next time.
+            if !status.success() {
+                remove_file(&filename).unwrap();
+                panic!("Failed to run {} on {}: {}", command, input, status);
+            }
+        }
+
+        let output = File::open(&filename).unwrap();
+        let lines = BufReader::new(output).lines().map(|l| l.unwrap());
+
+        match Self::parse(lines) {
+            Some(count) => count,
+            None => panic!("Failed to parse {}", filename),
+        }
+    }
+
+    fn filename(command: &str, input: &str) -> String {
+        let command = Path::new(command).file_name().unwrap().to_str().unwrap();
+
+        format!("{}.{}.output", input, command)
+    }
+
+    // ganak prints 's mc <count>' (or 's pmc' when projected) and sharpSAT
+    // prints the count on the line after '# solutions'.
+    fn parse<I: Iterator<Item=String>>(lines: I) -> Option<u128> {
+        let mut previous = String::new();
+
+        for line in lines {
+            let line = line.trim().to_string();
+
+            if line.starts_with("s mc ") || line.starts_with("s pmc ") {
+                return line.split_whitespace().last()?.parse().ok();
+            }
+
+            if previous == "# solutions" {
+                return line.parse().ok();
+            }
+
+            previous = line;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test;
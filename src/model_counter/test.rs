@@ -0,0 +1,61 @@
+use super::*;
+use std::env::temp_dir;
+use std::fs::{write, set_permissions, Permissions};
+use std::os::unix::fs::PermissionsExt;
+
+type Subject = ModelCounter;
+
+fn lines(text: &str) -> impl Iterator<Item=String> + '_ {
+    text.lines().map(String::from)
+}
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_count_from_ganak() {
+        let output = "c o Projection set size: 66\ns SATISFIABLE\nc s type pmc\ns pmc 8\n";
+
+        assert_eq!(Subject::parse(lines(output)), Some(8));
+    }
+
+    #[test]
+    fn it_parses_the_count_from_sharpsat() {
+        let output = "Solving file.dimacs\n# solutions \n1234\n# END\n";
+
+        assert_eq!(Subject::parse(lines(output)), Some(1234));
+    }
+
+    #[test]
+    fn it_returns_none_if_there_is_no_count() {
+        assert_eq!(Subject::parse(lines("c nothing here\n")), None);
+    }
+}
+
+mod count {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "Failed to run")]
+    fn it_panics_if_the_counter_fails() {
+        let counter = temp_dir().join("supersat-model-counter-crash.sh");
+        let counter = counter.to_str().unwrap().to_string();
+
+        write(&counter, "#!/bin/sh\necho 's mc 1'\nexit 1\n").unwrap();
+        set_permissions(&counter, Permissions::from_mode(0o755)).unwrap();
+
+        let input = temp_dir().join("supersat-model-counter-crash.dimacs");
+        let input = input.to_str().unwrap().to_string();
+
+        Subject::count(&counter, &input);
+    }
+}
+
+mod filename {
+    use super::*;
+
+    #[test]
+    fn it_names_the_output_after_the_command_without_its_directory() {
+        assert_eq!(Subject::filename("/opt/bin/ganak", "scratch/x.dimacs"), "scratch/x.dimacs.ganak.output");
+    }
+}
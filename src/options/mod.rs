@@ -0,0 +1,233 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::fs::read_to_string;
+
+use crate::problem_config::ProblemConfig;
+use crate::goal_direction::GoalDirection;
+
+#[derive(Debug, Default, Hash, Eq, PartialEq)]
+pub struct Options {
+    pub symbols: Option<usize>,
+    pub length: Option<usize>,
+    pub break_symmetry: bool,
+    pub prefix: Vec<usize>,
+    pub suffix: Vec<usize>,
+    pub fixed: Vec<(usize, usize)>,
+    pub substrings: Vec<Vec<usize>>,
+    pub repair: Vec<usize>,
+    pub window: Option<usize>,
+    pub enumerate: Option<usize>,
+    pub distinct: bool,
+    pub count: Option<usize>,
+    pub counter: Option<String>,
+    pub incremental: bool,
+    pub builtin: bool,
+    pub oracle: bool,
+    pub native_rows: Option<usize>,
+    pub known_rows: Option<usize>,
+    pub pb: Option<String>,
+    pub maxsat: Option<String>,
+    pub strategy: Option<String>,
+    pub portfolio: Vec<String>,
+    pub portfolio_encodings: bool,
+    pub cubes: Option<usize>,
+    pub jobs: Option<usize>,
+    pub proof: bool,
+    pub proof_solver: Option<String>,
+    pub check_proof: Option<String>,
+    pub solve: Option<String>,
+    pub compress: Option<String>,
+    pub preprocess: bool,
+    pub share_conditions: bool,
+    pub one_way_transitions: bool,
+    pub goal_direction: Option<String>,
+    pub dead_state_transitions: bool,
+    pub no_counter_overflow: bool,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Self {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || match args.next() {
+                Some(value) => value,
+                None => panic!("Missing value for {}", arg),
+            };
+
+            match arg.as_str() {
+                "--symbols" => options.symbols = Some(Self::number(value())),
+                "--length" => options.length = Some(Self::number(value())),
+                "--break-symmetry" => options.break_symmetry = true,
+                "--prefix" => options.prefix = Self::string(value()),
+                "--suffix" => options.suffix = Self::string(value()),
+                "--at" => options.fixed.push(Self::position(value())),
+                "--contains" => options.substrings.push(Self::string(value())),
+                "--repair" => options.repair = Self::string(value()),
+                "--window" => options.window = Some(Self::number(value())),
+                "--enumerate" => options.enumerate = Some(Self::number(value())),
+                "--distinct" => options.distinct = true,
+                "--count" => options.count = Some(Self::number(value())),
+                "--counter" => options.counter = Some(value().to_string()),
+                "--incremental" => options.incremental = true,
+                "--builtin" => options.builtin = true,
+                "--oracle" => options.oracle = true,
+                "--native-rows" => options.native_rows = Some(Self::number(value())),
+                "--known-rows" => options.known_rows = Some(Self::number(value())),
+                "--pb" => options.pb = Some(value().to_string()),
+                "--maxsat" => options.maxsat = Some(value().to_string()),
+                "--strategy" => options.strategy = Some(value().to_string()),
+                "--portfolio" => options.portfolio = value().split(',').map(String::from).collect(),
+                "--portfolio-encodings" => options.portfolio_encodings = true,
+                "--cubes" => options.cubes = Some(Self::number(value())),
+                "--jobs" => options.jobs = Some(Self::number(value())),
+                "--proof" => options.proof = true,
+                "--proof-solver" => options.proof_solver = Some(value().to_string()),
+                "--check-proof" => options.check_proof = Some(value().to_string()),
+                "--solve" => options.solve = Some(value().to_string()),
+                "--compress" => options.compress = Some(value().to_string()),
+                "--preprocess" => options.preprocess = true,
+                "--share-conditions" => options.share_conditions = true,
+                "--one-way-transitions" => options.one_way_transitions = true,
+                "--goal-direction" => options.goal_direction = Some(value().to_string()),
+                "--dead-state-transitions" => options.dead_state_transitions = true,
+                "--no-counter-overflow" => options.no_counter_overflow = true,
+                _ => panic!("Unknown argument {}", arg),
+            }
+        }
+
+        options
+    }
+
+    // Formulas are cached by filename so the constraints need to be part of it.
+    pub fn variant(&self) -> String {
+        self.variant_with(self.config())
+    }
+
+    // The portfolio can race encodings with other configs than the options ask
+    // for, each in its own file.
+    pub fn variant_with(&self, config: ProblemConfig) -> String {
+        let mut variant = String::new();
+
+        if self.break_symmetry {
+            variant.push_str("-symmetric");
+        }
+
+        if self.preprocess {
+            variant.push_str("-preprocessed");
+        }
+
+        if self.share_conditions {
+            variant.push_str("-shared-conditions");
+        }
+
+        if !config.bidirectional_transitions {
+            variant.push_str("-one-way-transitions");
+        }
+
+        if config.goal_direction != GoalDirection::Forward {
+            variant.push_str(&format!("-goal-{}", config.goal_direction));
+        }
+
+        if config.dead_state_transitions {
+            variant.push_str("-dead-state-transitions");
+        }
+
+        if !config.counter_overflow {
+            variant.push_str("-no-counter-overflow");
+        }
+
+        if self.is_constrained() {
+            let mut hasher = DefaultHasher::new();
+
+            self.prefix.hash(&mut hasher);
+            self.suffix.hash(&mut hasher);
+            self.fixed.hash(&mut hasher);
+            self.substrings.hash(&mut hasher);
+
+            variant.push_str(&format!("-constrained-{:x}", hasher.finish()));
+        }
+
+        variant
+    }
+
+    pub fn config(&self) -> ProblemConfig {
+        let goal_direction = match self.goal_direction.as_deref() {
+            None | Some("forward") => GoalDirection::Forward,
+            Some("backward") => GoalDirection::Backward,
+            Some("both") => GoalDirection::Both,
+            Some(direction) => panic!("Unknown goal direction {}", direction),
+        };
+
+        ProblemConfig {
+            bidirectional_transitions: !self.one_way_transitions,
+            goal_direction,
+            dead_state_transitions: self.dead_state_transitions,
+            counter_overflow: !self.no_counter_overflow,
+        }
+    }
+
+    pub fn is_constrained(&self) -> bool {
+        !self.prefix.is_empty() || !self.suffix.is_empty() ||
+            !self.fixed.is_empty() || !self.substrings.is_empty()
+    }
+
+    // Constraints can only use the symbols that are being searched for.
+    pub fn validate(&self, n: usize) {
+        if n < 2 {
+            panic!("Superpermutations need at least 2 symbols, not {}", n);
+        }
+
+        let mut strings = vec![("--prefix", &self.prefix), ("--suffix", &self.suffix)];
+        strings.extend(self.substrings.iter().map(|substring| ("--contains", substring)));
+
+        let fixed = self.fixed.iter().map(|(_, symbol)| ("--at", *symbol));
+        let symbols = strings.into_iter().flat_map(|(name, string)| string.iter().map(move |s| (name, *s)));
+
+        for (name, symbol) in symbols.chain(fixed) {
+            if symbol < 1 || symbol > n {
+                panic!("The symbol {} in {} is outside 1..={}", symbol, name, n);
+            }
+        }
+    }
+
+    fn number(text: &str) -> usize {
+        match text.trim().parse() {
+            Ok(number) => number,
+            Err(_) => panic!("Failed to parse {} as a number", text),
+        }
+    }
+
+    // Strings are written as digits, e.g. 123121321, or with commas between
+    // symbols. A leading @ reads the string from a file instead.
+    fn string(text: &str) -> Vec<usize> {
+        if let Some(filename) = text.strip_prefix('@') {
+            return match read_to_string(filename) {
+                Ok(contents) => Self::string(contents.trim()),
+                Err(_) => panic!("Failed to read {}", filename),
+            };
+        }
+
+        if text.contains(',') {
+            return text.split(',').map(Self::number).collect();
+        }
+
+        text.chars().filter(|c| !c.is_whitespace()).map(|c| match c.to_digit(10) {
+            Some(digit) => digit as usize,
+            None => panic!("Failed to parse {} as a string of symbols", text),
+        }).collect()
+    }
+
+    fn position(text: &str) -> (usize, usize) {
+        let parts = text.split('=').collect::<Vec<_>>();
+
+        match parts.as_slice() {
+            [time, symbol] => (Self::number(time), Self::number(symbol)),
+            _ => panic!("Failed to parse {} as TIME=SYMBOL", text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
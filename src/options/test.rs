@@ -0,0 +1,286 @@
+use super::*;
+
+type Subject = Options;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn it_has_no_constraints_by_default() {
+        let subject = Subject::parse(&[]);
+
+        assert_eq!(subject, Subject::default());
+        assert_eq!(subject.symbols, None);
+        assert!(!subject.break_symmetry);
+    }
+
+    #[test]
+    fn it_parses_the_number_of_symbols_and_symmetry_breaking() {
+        let subject = Subject::parse(&args(&["--symbols", "4", "--break-symmetry"]));
+
+        assert_eq!(subject.symbols, Some(4));
+        assert!(subject.break_symmetry);
+    }
+
+    #[test]
+    fn it_parses_the_length_of_a_constrained_query() {
+        let subject = Subject::parse(&args(&["--length", "33"]));
+
+        assert_eq!(subject.length, Some(33));
+    }
+
+    #[test]
+    fn it_parses_whether_to_solve_incrementally() {
+        let subject = Subject::parse(&args(&["--incremental"]));
+
+        assert!(subject.incremental);
+    }
+
+    #[test]
+    fn it_parses_strings_of_digits_or_comma_separated_symbols() {
+        let subject = Subject::parse(&args(&["--prefix", "12312", "--suffix", "2,1"]));
+
+        assert_eq!(subject.prefix, &[1, 2, 3, 1, 2]);
+        assert_eq!(subject.suffix, &[2, 1]);
+    }
+
+    #[test]
+    fn it_parses_fixed_positions_and_substrings_that_can_be_repeated() {
+        let subject = Subject::parse(&args(&[
+            "--at", "5=3", "--at", "7=1", "--contains", "321", "--contains", "132",
+        ]));
+
+        assert_eq!(subject.fixed, &[(5, 3), (7, 1)]);
+        assert_eq!(subject.substrings, &[vec![3, 2, 1], vec![1, 3, 2]]);
+    }
+
+    #[test]
+    fn it_parses_the_string_to_repair_and_the_window_size() {
+        let subject = Subject::parse(&args(&["--repair", "1231213213", "--window", "4"]));
+
+        assert_eq!(subject.repair, &[1, 2, 3, 1, 2, 1, 3, 2, 1, 3]);
+        assert_eq!(subject.window, Some(4));
+    }
+
+    #[test]
+    fn it_parses_the_length_to_enumerate_and_whether_to_remove_symmetries() {
+        let subject = Subject::parse(&args(&["--symbols", "4", "--enumerate", "33", "--distinct"]));
+
+        assert_eq!(subject.enumerate, Some(33));
+        assert!(subject.distinct);
+    }
+
+    #[test]
+    fn it_parses_the_length_to_count_and_the_model_counter_to_use() {
+        let subject = Subject::parse(&args(&["--count", "33", "--counter", "d4"]));
+
+        assert_eq!(subject.count, Some(33));
+        assert_eq!(subject.counter, Some("d4".to_string()));
+    }
+
+    #[test]
+    fn it_parses_the_search_strategy() {
+        let subject = Subject::parse(&args(&["--strategy", "galloping"]));
+
+        assert_eq!(subject.strategy, Some("galloping".to_string()));
+    }
+
+    #[test]
+    fn it_parses_the_solvers_to_race_in_a_portfolio() {
+        let subject = Subject::parse(&args(&["--portfolio", "lingeling,cadical", "--portfolio-encodings"]));
+
+        assert_eq!(subject.portfolio, vec!["lingeling".to_string(), "cadical".to_string()]);
+        assert!(subject.portfolio_encodings);
+    }
+
+    #[test]
+    fn it_parses_the_depth_of_the_cubes_and_the_number_of_jobs() {
+        let subject = Subject::parse(&args(&["--cubes", "3", "--jobs", "8"]));
+
+        assert_eq!(subject.cubes, Some(3));
+        assert_eq!(subject.jobs, Some(8));
+    }
+
+    #[test]
+    fn it_parses_whether_to_capture_and_check_proofs() {
+        let subject = Subject::parse(&args(&["--proof", "--proof-solver", "kissat", "--check-proof", "drat-trim"]));
+
+        assert!(subject.proof);
+        assert_eq!(subject.proof_solver, Some("kissat".to_string()));
+        assert_eq!(subject.check_proof, Some("drat-trim".to_string()));
+    }
+
+    #[test]
+    fn it_parses_the_dimacs_file_to_solve() {
+        let subject = Subject::parse(&args(&["--solve", "scratch/3-symbols-6-perms-3-waste.dimacs"]));
+
+        assert_eq!(subject.solve, Some("scratch/3-symbols-6-perms-3-waste.dimacs".to_string()));
+    }
+
+    #[test]
+    fn it_parses_the_compression_for_scratch_files() {
+        let subject = Subject::parse(&args(&["--compress", "zst"]));
+
+        assert_eq!(subject.compress, Some("zst".to_string()));
+    }
+
+    #[test]
+    fn it_parses_whether_to_preprocess_formulas() {
+        let subject = Subject::parse(&args(&["--preprocess"]));
+
+        assert!(subject.preprocess);
+    }
+
+    #[test]
+    fn it_parses_whether_to_share_conditions() {
+        let subject = Subject::parse(&args(&["--share-conditions"]));
+
+        assert!(subject.share_conditions);
+    }
+
+    #[test]
+    fn it_parses_the_choices_in_how_the_problem_is_encoded() {
+        let subject = Subject::parse(&args(&[
+            "--one-way-transitions",
+            "--goal-direction", "both",
+            "--dead-state-transitions",
+            "--no-counter-overflow",
+        ]));
+
+        assert!(subject.one_way_transitions);
+        assert_eq!(subject.goal_direction, Some("both".to_string()));
+        assert!(subject.dead_state_transitions);
+        assert!(subject.no_counter_overflow);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown argument --foo")]
+    fn it_panics_for_unknown_arguments() {
+        Subject::parse(&args(&["--foo"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing value for --prefix")]
+    fn it_panics_if_a_value_is_missing() {
+        Subject::parse(&args(&["--prefix"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to parse 5 as TIME=SYMBOL")]
+    fn it_panics_if_a_position_is_malformed() {
+        Subject::parse(&args(&["--at", "5"]));
+    }
+}
+
+mod config {
+    use super::*;
+
+    #[test]
+    fn it_is_the_default_without_any_options() {
+        assert_eq!(Subject::parse(&[]).config(), ProblemConfig::default());
+    }
+
+    #[test]
+    fn it_follows_the_choices_in_how_the_problem_is_encoded() {
+        let subject = Subject::parse(&args(&[
+            "--one-way-transitions",
+            "--goal-direction", "backward",
+            "--dead-state-transitions",
+            "--no-counter-overflow",
+        ]));
+
+        assert_eq!(subject.config(), ProblemConfig {
+            bidirectional_transitions: false,
+            goal_direction: GoalDirection::Backward,
+            dead_state_transitions: true,
+            counter_overflow: false,
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown goal direction sideways")]
+    fn it_panics_for_an_unknown_goal_direction() {
+        Subject::parse(&args(&["--goal-direction", "sideways"])).config();
+    }
+}
+
+mod variant {
+    use super::*;
+
+    #[test]
+    fn it_is_empty_without_any_options() {
+        assert_eq!(Subject::parse(&[]).variant(), "");
+    }
+
+    #[test]
+    fn it_includes_symmetry_breaking() {
+        let subject = Subject::parse(&args(&["--break-symmetry"]));
+
+        assert_eq!(subject.variant(), "-symmetric");
+    }
+
+    #[test]
+    fn it_includes_how_the_formula_is_encoded() {
+        let subject = Subject::parse(&args(&["--preprocess", "--share-conditions"]));
+
+        assert_eq!(subject.variant(), "-preprocessed-shared-conditions");
+    }
+
+    #[test]
+    fn it_includes_the_choices_in_how_the_problem_is_encoded() {
+        let subject = Subject::parse(&args(&["--goal-direction", "both", "--one-way-transitions"]));
+
+        assert_eq!(subject.variant(), "-one-way-transitions-goal-both");
+    }
+
+    #[test]
+    fn it_names_the_config_it_is_given_rather_than_the_options() {
+        let subject = Subject::parse(&args(&["--break-symmetry", "--one-way-transitions"]));
+        let config = ProblemConfig { dead_state_transitions: true, ..ProblemConfig::default() };
+
+        assert_eq!(subject.variant_with(config), "-symmetric-dead-state-transitions");
+    }
+
+    #[test]
+    fn it_distinguishes_between_different_constraints() {
+        let a = Subject::parse(&args(&["--prefix", "123"])).variant();
+        let b = Subject::parse(&args(&["--suffix", "123"])).variant();
+
+        assert!(a.starts_with("-constrained-"));
+        assert!(b.starts_with("-constrained-"));
+        assert_ne!(a, b);
+    }
+}
+
+mod validate {
+    use super::*;
+
+    #[test]
+    fn it_accepts_constraints_that_use_the_symbols() {
+        let subject = Subject::parse(&args(&["--prefix", "123", "--at", "5=3", "--contains", "321"]));
+
+        subject.validate(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Superpermutations need at least 2 symbols, not 1")]
+    fn it_panics_for_fewer_than_two_symbols() {
+        Subject::parse(&[]).validate(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "The symbol 4 in --suffix is outside 1..=3")]
+    fn it_panics_if_a_string_uses_another_symbol() {
+        Subject::parse(&args(&["--suffix", "1234"])).validate(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "The symbol 0 in --at is outside 1..=3")]
+    fn it_panics_if_a_fixed_position_uses_another_symbol() {
+        Subject::parse(&args(&["--at", "2=0"])).validate(3);
+    }
+}
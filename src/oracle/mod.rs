@@ -0,0 +1,102 @@
+use crate::utility::Utility;
+
+// Computes the maximum number of permutations for each number of wasted
+// symbols by searching over strings directly, without going through SAT. It
+// follows the same rules as the encoding: the string starts with ascending
+// numbers, no permutation may appear twice and a symbol is wasted when the
+// window ending at it isn't a permutation.
+pub struct Oracle {
+    n: usize,
+    string: Vec<usize>,
+    seen: Vec<bool>,
+    max_permutations: Vec<usize>,
+}
+
+impl Oracle {
+    pub fn new(n: usize) -> Self {
+        let string = (1..=n).collect::<Vec<_>>();
+        let seen = vec![false; (n + 1).pow(n as u32)];
+
+        Self { n, string, seen, max_permutations: vec![] }
+    }
+
+    pub fn max_permutations(&mut self, rows: usize) -> Vec<usize> {
+        let all_permutations = Utility::factorial(self.n);
+
+        while self.max_permutations.len() < rows {
+            if self.max_permutations.last() == Some(&all_permutations) {
+                break;
+            }
+
+            let wasted_symbols = self.max_permutations.len();
+            let mut best = self.max_permutations.last().cloned().unwrap_or(0);
+
+            let first = self.window_index(self.n);
+            self.seen[first] = true;
+            self.search(1, 0, wasted_symbols, &mut best);
+            self.seen[first] = false;
+
+            self.max_permutations.push(best);
+        }
+
+        self.max_permutations.clone()
+    }
+
+    fn search(&mut self, permutations: usize, wasted_symbols: usize, limit: usize, best: &mut usize) {
+        if permutations > *best {
+            *best = permutations;
+        }
+
+        if permutations == Utility::factorial(self.n) {
+            return;
+        }
+
+        // Whatever follows can't add more permutations than the best string
+        // with the remaining wasted symbols, which is known from earlier rows.
+        let remaining = limit - wasted_symbols;
+
+        if let Some(additional) = self.max_permutations.get(remaining) {
+            if permutations + additional <= *best {
+                return;
+            }
+        }
+
+        for symbol in 1..=self.n {
+            self.string.push(symbol);
+
+            let index = self.window_index(self.string.len());
+
+            if self.is_permutation(index) {
+                if !self.seen[index] {
+                    self.seen[index] = true;
+                    self.search(permutations + 1, wasted_symbols, limit, best);
+                    self.seen[index] = false;
+                }
+            } else if wasted_symbols < limit {
+                self.search(permutations, wasted_symbols + 1, limit, best);
+            }
+
+            self.string.pop();
+        }
+    }
+
+    // Windows are numbered by reading their symbols in base n + 1.
+    fn window_index(&self, end: usize) -> usize {
+        self.string[(end - self.n)..end].iter().fold(0, |index, s| index * (self.n + 1) + s)
+    }
+
+    fn is_permutation(&self, index: usize) -> bool {
+        let mut remaining = index;
+        let mut symbols = 0_usize;
+
+        for _ in 0..self.n {
+            symbols |= 1 << (remaining % (self.n + 1));
+            remaining /= self.n + 1;
+        }
+
+        symbols.count_ones() as usize == self.n
+    }
+}
+
+#[cfg(test)]
+mod test;
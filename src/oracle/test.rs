@@ -0,0 +1,91 @@
+use super::*;
+use crate::formula::Formula;
+use crate::machine::Machine;
+use crate::goal::Goal;
+use crate::bounds::Bounds;
+use crate::logic::Logic;
+use crate::problem::Problem;
+use crate::backend::Backend;
+use crate::cdcl::Cdcl;
+
+type Subject = Oracle;
+
+mod max_permutations {
+    use super::*;
+
+    #[test]
+    fn it_finds_the_maximum_number_of_permutations_for_each_number_of_wasted_symbols() {
+        assert_eq!(Subject::new(2).max_permutations(10), vec![2]);
+        assert_eq!(Subject::new(3).max_permutations(10), vec![3, 6]);
+        assert_eq!(Subject::new(4).max_permutations(10), vec![4, 8, 12, 14, 18, 20, 24]);
+    }
+
+    #[test]
+    fn it_only_computes_the_number_of_rows_requested() {
+        assert_eq!(Subject::new(5).max_permutations(5), vec![5, 10, 15, 20, 23]);
+    }
+
+    #[test]
+    fn it_continues_from_the_rows_it_has_already_computed() {
+        let mut subject = Subject::new(4);
+
+        assert_eq!(subject.max_permutations(2), vec![4, 8]);
+        assert_eq!(subject.max_permutations(3), vec![4, 8, 12]);
+    }
+}
+
+// Every answer the SAT encoding gives when main builds up the table should
+// agree with the oracle: the maximum is reachable but one more isn't.
+mod cross_check {
+    use super::*;
+
+    fn satisfiable(n: usize, permutations: usize, max_permutations: &[usize]) -> bool {
+        let wasted_symbols = n - 1 + max_permutations.len();
+        let length_of_string = permutations + wasted_symbols;
+
+        let mut formula = Formula::new();
+        let machine = Machine::new(n, length_of_string, &mut formula);
+        let goal = Goal::new(n, length_of_string, &mut formula);
+        let bounds = Bounds::new(n, length_of_string, max_permutations);
+        let mut logic = Logic::new(&mut formula);
+        let mut problem = Problem::new(n, length_of_string, &machine, &goal, &bounds, &mut logic);
+
+        problem.the_machine_starts_in_the_dead_states();
+        problem.the_machine_changes_state_when_it_reads_input();
+        problem.each_permutation_appears_at_most_once();
+        problem.all_binary_representations_map_to_states();
+        problem.the_string_starts_with_ascending_numbers();
+        problem.the_number_of_wasted_symbols_is_within_bounds();
+
+        Cdcl::new().solve(&formula, &[]).is_some()
+    }
+
+    fn assert_encoding_agrees(n: usize, rows: usize) {
+        let expected = Subject::new(n).max_permutations(rows);
+
+        for (row, &permutations) in expected.iter().enumerate() {
+            let max_permutations = &expected[..row];
+
+            assert!(satisfiable(n, permutations, max_permutations));
+
+            if permutations < Utility::factorial(n) {
+                assert!(!satisfiable(n, permutations + 1, max_permutations));
+            }
+        }
+    }
+
+    #[test]
+    fn it_agrees_with_the_sat_encoding_for_n_equals_3() {
+        assert_encoding_agrees(3, 10);
+    }
+
+    #[test]
+    fn it_agrees_with_the_sat_encoding_for_n_equals_4() {
+        assert_encoding_agrees(4, 10);
+    }
+
+    #[test]
+    fn it_agrees_with_the_sat_encoding_for_the_first_row_of_n_equals_5() {
+        assert_encoding_agrees(5, 1);
+    }
+}
@@ -0,0 +1,95 @@
+use std::process::{Child, Command};
+
+use std::fs::remove_file;
+use std::path::Path;
+use std::thread::sleep;
+use std::time::Duration;
+
+use crate::model::Model;
+use crate::solver::Solver;
+use crate::compression::{Compression, Compressor};
+
+// Races several solvers, or the same solver on different encodings of the
+// query, as child processes. The first to give a definitive answer wins and
+// the rest are killed. Each entry is a command and the file to run it on.
+pub struct Portfolio {
+
+}
+
+impl Portfolio {
+    pub fn solve(entries: &[(String, String)]) -> Option<Model> {
+        for (command, input) in entries {
+            if let Some(model) = Solver::answer(&Self::filename(command, input)) {
+                return model;
+            }
+        }
+
+        let mut children = entries.iter().map(|(command, input)| {
+            let filename = Self::filename(command, input);
+            let (output, compressor) = Compression::stdio(&filename);
+
+            let child = match Command::new(command).arg(input).stdout(output).spawn() {
+                Ok(child) => child,
+                Err(_) => panic!("Failed to run {}", command),
+            };
+
+            (child, command, filename, compressor)
+        }).collect::<Vec<_>>();
+
+        loop {
+            let mut index = 0;
+
+            while index < children.len() {
+                let (child, command, filename, compressor) = &mut children[index];
+
+                if child.try_wait().unwrap().is_none() {
+                    index += 1;
+                    continue;
+                }
+
+                compressor.take();
+
+                if let Some(model) = Solver::answer(filename) {
+                    println!("{} answered first with {}.", command, filename);
+
+                    children.remove(index);
+                    Self::kill(children);
+
+                    return model;
+                }
+
+                // The solver crashed or gave up so its output is no use.
+                remove_file(&filename).unwrap();
+                children.remove(index);
+            }
+
+            if children.is_empty() {
+                panic!("Failed to get an answer from any solver in the portfolio");
+            }
+
+            sleep(Duration::from_millis(10));
+        }
+    }
+
+    // Partial output would be mistaken for an answer next time so remove it.
+    fn kill(children: Vec<(Child, &String, String, Option<Compressor>)>) {
+        for (mut child, _, filename, compressor) in children {
+            child.kill().ok();
+            child.wait().unwrap();
+            drop(compressor);
+
+            if Path::new(&filename).exists() {
+                remove_file(&filename).unwrap();
+            }
+        }
+    }
+
+    fn filename(command: &str, input: &str) -> String {
+        let command = Path::new(command).file_name().unwrap().to_str().unwrap();
+
+        Compression::append(input, &format!(".{}.output", command))
+    }
+}
+
+#[cfg(test)]
+mod test;
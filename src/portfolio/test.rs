@@ -0,0 +1,106 @@
+use super::*;
+use std::env::temp_dir;
+use std::fs::{read, rename, write};
+use std::time::Instant;
+use crate::literal::Literal;
+
+type Subject = Portfolio;
+
+// Writes a shell script that stands in for a solver, which sh runs as if it
+// were the input file.
+fn script(name: &str, contents: &str) -> (String, String) {
+    let filename = temp_dir().join(format!("supersat-portfolio-{}.sh", name));
+    let filename = filename.to_str().unwrap().to_string();
+
+    write(&filename, contents).unwrap();
+
+    ("sh".to_string(), filename)
+}
+
+fn clean_up(entries: &[(String, String)]) {
+    for (command, input) in entries {
+        let output = Subject::filename(command, input);
+
+        if Path::new(&output).exists() {
+            remove_file(&output).unwrap();
+        }
+
+        remove_file(input).unwrap();
+    }
+}
+
+mod solve {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_first_definitive_answer_and_kills_the_others() {
+        let entries = vec![
+            script("slow", "sleep 30\necho 's UNSATISFIABLE'\n"),
+            script("fast", "echo 's SATISFIABLE'\necho 'v 1 -2 0'\n"),
+        ];
+
+        let started = Instant::now();
+        let model = Subject::solve(&entries);
+
+        assert!(started.elapsed().as_secs() < 30);
+        assert_eq!(model, Some(Model::new(&[Literal::from_dimacs(1), Literal::from_dimacs(-2)])));
+
+        let (command, input) = &entries[0];
+        assert!(!Path::new(&Subject::filename(command, input)).exists());
+
+        clean_up(&entries);
+    }
+
+    #[test]
+    fn it_ignores_solvers_that_finish_without_an_answer() {
+        let entries = vec![
+            script("crash", "echo 'c out of memory'\n"),
+            script("unsatisfiable", "sleep 1\necho 's UNSATISFIABLE'\n"),
+        ];
+
+        assert_eq!(Subject::solve(&entries), None);
+
+        clean_up(&entries);
+    }
+
+    #[test]
+    fn it_reuses_an_answer_from_a_previous_race() {
+        let entries = vec![script("cached", "echo 's UNSATISFIABLE'\n")];
+
+        assert_eq!(Subject::solve(&entries), None);
+
+        let (_, input) = &entries[0];
+        write(input, "echo 's SATISFIABLE'\n").unwrap();
+
+        assert_eq!(Subject::solve(&entries), None);
+
+        clean_up(&entries);
+    }
+
+    #[test]
+    fn it_compresses_the_outputs_if_the_input_is_compressed() {
+        let (command, input) = script("compressed", "echo 's UNSATISFIABLE'\n");
+        let entries = vec![(command, format!("{}.gz", input))];
+
+        rename(&input, &entries[0].1).unwrap();
+
+        assert_eq!(Subject::solve(&entries), None);
+
+        let (command, input) = &entries[0];
+        let output = Subject::filename(command, input);
+
+        // Gzip files start with the magic number 1f 8b.
+        assert!(output.ends_with(".output.gz"));
+        assert_eq!(&read(&output).unwrap()[..2], &[0x1f, 0x8b]);
+
+        clean_up(&entries);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to get an answer from any solver in the portfolio")]
+    fn it_panics_if_no_solver_gives_an_answer() {
+        let entries = vec![script("gives-up", "echo 's UNKNOWN'\n")];
+
+        Subject::solve(&entries);
+    }
+}
@@ -0,0 +1,512 @@
+use crate::formula::Formula;
+use crate::clause::Clause;
+use crate::variable::Variable;
+use crate::literal::Literal;
+use crate::model::Model;
+
+const ROUNDS: usize = 5;
+const ELIMINATION_LIMIT: usize = 20;
+
+// Simplifies a formula with unit propagation, subsumption, self-subsuming
+// resolution, equivalent literal substitution and bounded variable elimination.
+// The simplified formula is equisatisfiable and keeps the variable numbering.
+// Every clause that's removed along with a variable is pushed onto a stack with
+// the literal that satisfies it, so a model of the simplified formula can be
+// turned back into a model of the original, e.g. for Machine::decode. Literals
+// are DIMACS numbers and are coded as in Cdcl for the occurrence lists.
+pub struct Preprocessor {
+    number_of_variables: usize,
+    clauses: Vec<Vec<isize>>,
+    removed: Vec<bool>,
+    occurrences: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    frozen: Vec<bool>,
+    units: Vec<isize>,
+    stack: Vec<(isize, Vec<isize>)>,
+    unsatisfiable: bool,
+    comments: Vec<String>,
+}
+
+impl Preprocessor {
+    pub fn new(formula: &Formula) -> Self {
+        let number_of_variables = formula.number_of_variables();
+
+        let mut preprocessor = Self {
+            number_of_variables,
+            clauses: vec![],
+            removed: vec![],
+            occurrences: vec![vec![]; 2 * number_of_variables],
+            values: vec![None; number_of_variables + 1],
+            frozen: vec![false; number_of_variables + 1],
+            units: vec![],
+            stack: vec![],
+            unsatisfiable: false,
+            comments: formula.comments().to_vec(),
+        };
+
+        // Clauses are sorted so the result doesn't depend on the hash order.
+        let mut clauses = formula.clauses().iter().map(|clause| {
+            clause.literals().iter().map(Self::number).collect::<Vec<_>>()
+        }).collect::<Vec<_>>();
+
+        clauses.sort();
+
+        for clause in clauses {
+            preprocessor.add(clause);
+        }
+
+        preprocessor.propagate();
+        preprocessor
+    }
+
+    // Frozen variables keep their meaning in the simplified formula so they
+    // can be used in assumptions.
+    pub fn freeze(&mut self, variables: &[Variable]) {
+        for variable in variables {
+            self.frozen[variable.number] = true;
+        }
+    }
+
+    pub fn simplify(&mut self) {
+        for _ in 0..ROUNDS {
+            let before = self.size();
+
+            self.propagate();
+            self.subsume();
+            self.substitute();
+            self.eliminate();
+
+            if self.unsatisfiable || self.size() == before {
+                break;
+            }
+        }
+    }
+
+    pub fn formula(&self) -> Formula {
+        let mut formula = Formula::new();
+
+        for _ in 0..self.number_of_variables {
+            formula.new_variable();
+        }
+
+        for comment in &self.comments {
+            formula.add_comment(comment.clone());
+        }
+
+        if self.unsatisfiable {
+            formula.add_clause(Clause::new());
+            return formula;
+        }
+
+        for (index, numbers) in self.clauses.iter().enumerate() {
+            if self.removed[index] {
+                continue;
+            }
+
+            let mut clause = Clause::new();
+
+            for number in numbers {
+                clause.add(Literal::from_dimacs(*number));
+            }
+
+            formula.add_clause(clause);
+        }
+
+        // Assumptions about frozen variables have to see what they were set to.
+        for (variable, value) in self.values.iter().enumerate() {
+            if let (true, Some(value)) = (self.frozen[variable], value) {
+                let mut clause = Clause::new();
+                clause.add(Literal::new(Variable::new(variable), *value));
+                formula.add_clause(clause);
+            }
+        }
+
+        formula
+    }
+
+    // Undoes the simplifications in reverse, flipping each removed variable
+    // if a clause it was removed with isn't satisfied.
+    pub fn reconstruct(&self, model: &Model) -> Model {
+        let mut values = (0..=self.number_of_variables).map(|number| {
+            number > 0 && model.satisfies(&[Literal::positive(Variable::new(number))])
+        }).collect::<Vec<_>>();
+
+        for (witness, clause) in self.stack.iter().rev() {
+            if !clause.iter().any(|n| values[n.unsigned_abs()] == (*n > 0)) {
+                values[witness.unsigned_abs()] = *witness > 0;
+            }
+        }
+
+        let literals = (1..=self.number_of_variables)
+            .map(|number| Literal::new(Variable::new(number), values[number]))
+            .collect::<Vec<_>>();
+
+        Model::new(&literals)
+    }
+
+    fn number(literal: &Literal) -> isize {
+        let number = literal.variable.number as isize;
+        if literal.positive { number } else { -number }
+    }
+
+    fn code(number: isize) -> usize {
+        2 * (number.unsigned_abs() - 1) + (number < 0) as usize
+    }
+
+    fn value(&self, number: isize) -> Option<bool> {
+        self.values[number.unsigned_abs()].map(|value| value == (number > 0))
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let live = (0..self.clauses.len()).filter(|i| !self.removed[*i]);
+        let literals = live.clone().map(|i| self.clauses[i].len()).sum();
+
+        (live.count(), literals)
+    }
+
+    // The clauses that still contain the literal, since occurrence lists are
+    // only cleaned up lazily.
+    fn occurring(&self, number: isize) -> Vec<usize> {
+        self.occurrences[Self::code(number)].iter().cloned()
+            .filter(|i| !self.removed[*i] && self.clauses[*i].contains(&number))
+            .collect()
+    }
+
+    fn add(&mut self, mut clause: Vec<isize>) {
+        clause.sort_unstable();
+        clause.dedup();
+
+        if clause.iter().any(|n| clause.contains(&-n)) {
+            return;
+        }
+
+        if clause.iter().any(|n| self.value(*n) == Some(true)) {
+            return;
+        }
+
+        clause.retain(|n| self.value(*n).is_none());
+
+        match clause.len() {
+            0 => self.unsatisfiable = true,
+            1 => self.assign(clause[0]),
+            _ => {
+                let index = self.clauses.len();
+
+                for number in &clause {
+                    self.occurrences[Self::code(*number)].push(index);
+                }
+
+                self.clauses.push(clause);
+                self.removed.push(false);
+            },
+        }
+    }
+
+    fn assign(&mut self, number: isize) {
+        match self.value(number) {
+            Some(true) => {},
+            Some(false) => self.unsatisfiable = true,
+            None => {
+                self.values[number.unsigned_abs()] = Some(number > 0);
+                self.stack.push((number, vec![number]));
+                self.units.push(number);
+            },
+        }
+    }
+
+    fn propagate(&mut self) {
+        while let Some(number) = self.units.pop() {
+            if self.unsatisfiable {
+                return;
+            }
+
+            for index in self.occurring(number) {
+                self.removed[index] = true;
+            }
+
+            for index in self.occurring(-number) {
+                self.strengthen(index, -number);
+            }
+        }
+    }
+
+    fn strengthen(&mut self, index: usize, number: isize) {
+        self.clauses[index].retain(|n| *n != number);
+
+        match self.clauses[index].len() {
+            0 => self.unsatisfiable = true,
+            1 => {
+                self.removed[index] = true;
+                self.assign(self.clauses[index][0]);
+            },
+            _ => {},
+        }
+    }
+
+    // Any clause that the clause subsumes, or strengthens by self-subsuming
+    // resolution, contains its rarest variable so only those are checked.
+    fn subsume(&mut self) {
+        let mut order = (0..self.clauses.len()).filter(|i| !self.removed[*i]).collect::<Vec<_>>();
+        order.sort_by_key(|i| self.clauses[*i].len());
+
+        for index in order {
+            if self.removed[index] || self.unsatisfiable {
+                continue;
+            }
+
+            let clause = self.clauses[index].clone();
+
+            let rarest = *clause.iter().min_by_key(|n| {
+                self.occurrences[Self::code(**n)].len() + self.occurrences[Self::code(-**n)].len()
+            }).unwrap();
+
+            let mut candidates = self.occurring(rarest);
+            candidates.extend(self.occurring(-rarest));
+
+            for other in candidates {
+                if other == index || self.removed[other] || self.clauses[other].len() < clause.len() {
+                    continue;
+                }
+
+                match Self::subsumes(&clause, &self.clauses[other]) {
+                    Some(None) => self.removed[other] = true,
+                    Some(Some(number)) => self.strengthen(other, -number),
+                    None => {},
+                }
+            }
+
+            self.propagate();
+        }
+    }
+
+    // Returns Some(None) if the clause subsumes the other and Some(Some(n)) if
+    // it would with n negated, in which case -n can be removed from the other.
+    fn subsumes(clause: &[isize], other: &[isize]) -> Option<Option<isize>> {
+        let mut flipped = None;
+
+        for number in clause {
+            if other.binary_search(number).is_ok() {
+                continue;
+            }
+
+            if flipped.is_none() && other.binary_search(&-number).is_ok() {
+                flipped = Some(*number);
+                continue;
+            }
+
+            return None;
+        }
+
+        Some(flipped)
+    }
+
+    // Literals in the same strongly connected component of the binary
+    // implication graph are equivalent, so each is replaced by the one with
+    // the lowest variable, or a frozen one.
+    fn substitute(&mut self) {
+        if self.unsatisfiable {
+            return;
+        }
+
+        let mut edges = vec![vec![]; 2 * self.number_of_variables];
+
+        for (index, clause) in self.clauses.iter().enumerate() {
+            if !self.removed[index] && clause.len() == 2 {
+                edges[Self::code(-clause[0])].push(clause[1]);
+                edges[Self::code(-clause[1])].push(clause[0]);
+            }
+        }
+
+        let mut representatives = vec![0; self.number_of_variables + 1];
+
+        for component in Self::components(self.number_of_variables, &edges) {
+            if component.iter().any(|n| component.contains(&-n)) {
+                self.unsatisfiable = true;
+                return;
+            }
+
+            let representative = *component.iter().min_by_key(|n| (!self.frozen[n.unsigned_abs()], n.unsigned_abs())).unwrap();
+
+            for number in component {
+                let variable = number.unsigned_abs();
+
+                if number != representative && !self.frozen[variable] {
+                    representatives[variable] = if number > 0 { representative } else { -representative };
+                }
+            }
+        }
+
+        for (variable, &representative) in representatives.iter().enumerate() {
+            if representative == 0 {
+                continue;
+            }
+
+            let number = variable as isize;
+            let mut indices = self.occurring(number);
+            indices.extend(self.occurring(-number));
+
+            self.stack.push((number, vec![number, -representative]));
+            self.stack.push((-number, vec![-number, representative]));
+
+            for index in indices {
+                let clause = self.clauses[index].iter().map(|n| match *n {
+                    n if n == number => representative,
+                    n if n == -number => -representative,
+                    n => n,
+                }).collect();
+
+                self.removed[index] = true;
+                self.add(clause);
+            }
+        }
+
+        self.propagate();
+    }
+
+    // Tarjan's algorithm without recursion, since the graphs are large.
+    fn components(number_of_variables: usize, edges: &[Vec<isize>]) -> Vec<Vec<isize>> {
+        let number = |code: usize| {
+            let variable = (code / 2 + 1) as isize;
+            if code & 1 == 0 { variable } else { -variable }
+        };
+
+        let size = 2 * number_of_variables;
+        let mut indices = vec![usize::MAX; size];
+        let mut lowlinks = vec![0; size];
+        let mut on_stack = vec![false; size];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut counter = 0;
+
+        for root in 0..size {
+            if indices[root] != usize::MAX || edges[root].is_empty() {
+                continue;
+            }
+
+            let mut calls = vec![(root, 0)];
+
+            while let Some((code, edge)) = calls.pop() {
+                if edge == 0 {
+                    indices[code] = counter;
+                    lowlinks[code] = counter;
+                    counter += 1;
+                    stack.push(code);
+                    on_stack[code] = true;
+                } else {
+                    let child = Self::code(edges[code][edge - 1]);
+                    lowlinks[code] = lowlinks[code].min(lowlinks[child]);
+                }
+
+                let mut next = edge;
+                let mut descended = false;
+
+                while next < edges[code].len() {
+                    let child = Self::code(edges[code][next]);
+                    next += 1;
+
+                    if indices[child] == usize::MAX {
+                        calls.push((code, next));
+                        calls.push((child, 0));
+                        descended = true;
+                        break;
+                    } else if on_stack[child] {
+                        lowlinks[code] = lowlinks[code].min(indices[child]);
+                    }
+                }
+
+                if descended || lowlinks[code] != indices[code] {
+                    continue;
+                }
+
+                let mut component = vec![];
+
+                loop {
+                    let member = stack.pop().unwrap();
+                    on_stack[member] = false;
+                    component.push(number(member));
+
+                    if member == code {
+                        break;
+                    }
+                }
+
+                if component.len() > 1 {
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    // A variable is eliminated by replacing its clauses with their resolvents,
+    // as long as that doesn't add clauses.
+    fn eliminate(&mut self) {
+        for variable in 1..=self.number_of_variables {
+            if self.unsatisfiable {
+                return;
+            }
+
+            if self.frozen[variable] || self.values[variable].is_some() {
+                continue;
+            }
+
+            let number = variable as isize;
+            let positive = self.occurring(number);
+            let negative = self.occurring(-number);
+
+            if positive.is_empty() && negative.is_empty() {
+                continue;
+            }
+
+            if positive.len() + negative.len() > ELIMINATION_LIMIT {
+                continue;
+            }
+
+            let limit = positive.len() + negative.len();
+            let mut resolvents = vec![];
+
+            'outer: for p in &positive {
+                for n in &negative {
+                    let mut resolvent = self.clauses[*p].iter().chain(&self.clauses[*n])
+                        .cloned()
+                        .filter(|l| l.unsigned_abs() != variable)
+                        .collect::<Vec<_>>();
+
+                    resolvent.sort_unstable();
+                    resolvent.dedup();
+
+                    if resolvent.iter().any(|l| resolvent.binary_search(&-l).is_ok()) {
+                        continue;
+                    }
+
+                    resolvents.push(resolvent);
+
+                    if resolvents.len() > limit {
+                        break 'outer;
+                    }
+                }
+            }
+
+            if resolvents.len() > limit {
+                continue;
+            }
+
+            for (indices, witness) in [(positive, number), (negative, -number)] {
+                for index in indices {
+                    self.stack.push((witness, self.clauses[index].clone()));
+                    self.removed[index] = true;
+                }
+            }
+
+            for resolvent in resolvents {
+                self.add(resolvent);
+            }
+
+            self.propagate();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,209 @@
+use super::*;
+use crate::machine::Machine;
+use crate::goal::Goal;
+use crate::bounds::Bounds;
+use crate::logic::Logic;
+use crate::problem::Problem;
+use crate::backend::Backend;
+use crate::cdcl::Cdcl;
+use crate::test_formulas::{formula, satisfies, Random};
+
+type Subject = Preprocessor;
+
+mod new {
+    use super::*;
+
+    #[test]
+    fn it_propagates_the_unit_clauses() {
+        let subject = Subject::new(&formula(4, &[vec![1], vec![-1, 2], vec![-2, 3, 4], vec![-3, 1]]));
+
+        assert_eq!(subject.formula(), formula(4, &[vec![3, 4]]));
+    }
+
+    #[test]
+    fn it_detects_conflicting_unit_clauses() {
+        let subject = Subject::new(&formula(2, &[vec![1], vec![-1, 2], vec![-2]]));
+
+        assert_eq!(Cdcl::new().solve(&subject.formula(), &[]), None);
+    }
+}
+
+mod subsume {
+    use super::*;
+
+    #[test]
+    fn it_removes_subsumed_clauses() {
+        let mut subject = Subject::new(&formula(3, &[vec![1, 2], vec![1, 2, 3]]));
+        subject.subsume();
+
+        assert_eq!(subject.formula(), formula(3, &[vec![1, 2]]));
+    }
+
+    #[test]
+    fn it_strengthens_clauses_by_self_subsuming_resolution() {
+        let mut subject = Subject::new(&formula(3, &[vec![1, 2], vec![-1, 2, 3]]));
+        subject.subsume();
+
+        assert_eq!(subject.formula(), formula(3, &[vec![1, 2], vec![2, 3]]));
+    }
+}
+
+mod substitute {
+    use super::*;
+
+    #[test]
+    fn it_replaces_equivalent_literals_with_the_lowest_variable() {
+        let mut subject = Subject::new(&formula(5, &[vec![-1, 2], vec![1, -2], vec![2, 3], vec![-2, 4, 5]]));
+        subject.substitute();
+
+        assert_eq!(subject.formula(), formula(5, &[vec![1, 3], vec![-1, 4, 5]]));
+    }
+
+    #[test]
+    fn it_keeps_frozen_variables() {
+        let mut subject = Subject::new(&formula(3, &[vec![-1, 2], vec![1, -2], vec![2, 3]]));
+        subject.freeze(&[Variable::new(2)]);
+        subject.substitute();
+
+        assert_eq!(subject.formula(), formula(3, &[vec![2, 3]]));
+    }
+
+    #[test]
+    fn it_detects_a_literal_that_is_equivalent_to_its_negation() {
+        let mut subject = Subject::new(&formula(2, &[vec![-1, 2], vec![1, -2], vec![1, 2], vec![-1, -2]]));
+        subject.substitute();
+
+        assert_eq!(Cdcl::new().solve(&subject.formula(), &[]), None);
+    }
+}
+
+mod eliminate {
+    use super::*;
+
+    #[test]
+    fn it_replaces_a_variables_clauses_with_their_resolvents() {
+        let mut subject = Subject::new(&formula(3, &[vec![1, 2], vec![-1, 3]]));
+        subject.freeze(&[Variable::new(2), Variable::new(3)]);
+        subject.eliminate();
+
+        assert_eq!(subject.formula(), formula(3, &[vec![2, 3]]));
+    }
+
+    #[test]
+    fn it_keeps_variables_whose_elimination_would_add_clauses() {
+        let clauses = vec![vec![1, 2], vec![1, 3], vec![1, 4], vec![-1, 5], vec![-1, 6]];
+        let frozen = (2..=6).map(Variable::new).collect::<Vec<_>>();
+
+        let mut subject = Subject::new(&formula(6, &clauses));
+        subject.freeze(&frozen);
+        subject.eliminate();
+
+        assert_eq!(subject.formula(), formula(6, &clauses));
+    }
+}
+
+mod formula {
+    use super::*;
+
+    #[test]
+    fn it_keeps_the_units_of_frozen_variables() {
+        let mut subject = Subject::new(&formula(3, &[vec![1], vec![-1, 2, 3]]));
+        subject.freeze(&[Variable::new(1)]);
+        subject.simplify();
+
+        let simplified = subject.formula();
+        let mut cdcl = Cdcl::new();
+
+        assert_eq!(cdcl.solve(&simplified, &[Literal::negative(Variable::new(1))]), None);
+        assert!(cdcl.solve(&simplified, &[Literal::positive(Variable::new(1))]).is_some());
+    }
+
+    #[test]
+    fn it_keeps_the_comments() {
+        let mut original = formula(2, &[vec![1, 2]]);
+        original.add_comment("config foo=true".to_string());
+
+        let mut subject = Subject::new(&original);
+        subject.simplify();
+
+        assert_eq!(subject.formula().comments(), ["config foo=true".to_string()]);
+    }
+}
+
+mod reconstruct {
+    use super::*;
+
+    #[test]
+    fn it_extends_a_model_of_the_simplified_formula_to_the_original() {
+        let clauses = vec![vec![1, 2], vec![-1, 3], vec![-2, -3], vec![4], vec![-4, 5, 1]];
+
+        let mut subject = Subject::new(&formula(5, &clauses));
+        subject.simplify();
+
+        let model = Cdcl::new().solve(&subject.formula(), &[]).unwrap();
+
+        assert!(satisfies(&subject.reconstruct(&model), &clauses));
+    }
+
+    #[test]
+    fn it_agrees_with_the_original_formula_on_random_formulas() {
+        let mut random = Random::new(2468);
+
+        for round in 0..200 {
+            let clauses = (0..(20 + round % 40)).map(|_| {
+                let width = 1 + random.below(3);
+                random.clause(width, 10)
+            }).collect::<Vec<_>>();
+
+            let original = formula(10, &clauses);
+            let mut subject = Subject::new(&original);
+            subject.simplify();
+
+            let expected = Cdcl::new().solve(&original, &[]).is_some();
+            let model = Cdcl::new().solve(&subject.formula(), &[]);
+
+            assert_eq!(model.is_some(), expected);
+
+            if let Some(model) = model {
+                assert!(satisfies(&subject.reconstruct(&model), &clauses));
+            }
+        }
+    }
+
+    // The machine's variables may be eliminated but the string can still be
+    // decoded from the reconstructed model.
+    #[test]
+    fn it_lets_the_machine_decode_the_string() {
+        let length_of_string = 9;
+
+        let mut formula = Formula::new();
+        let machine = Machine::new(3, length_of_string, &mut formula);
+        let goal = Goal::new(3, length_of_string, &mut formula);
+        let bounds = Bounds::new(3, length_of_string, &[3]);
+        let mut logic = Logic::new(&mut formula);
+        let mut problem = Problem::new(3, length_of_string, &machine, &goal, &bounds, &mut logic);
+
+        problem.the_machine_starts_in_the_dead_states();
+        problem.the_machine_changes_state_when_it_reads_input();
+        problem.each_permutation_appears_at_most_once();
+        problem.all_binary_representations_map_to_states();
+        problem.the_string_starts_with_ascending_numbers();
+        problem.the_number_of_wasted_symbols_is_within_bounds();
+
+        let mut subject = Subject::new(&formula);
+        subject.simplify();
+
+        let simplified = subject.formula();
+        assert!(simplified.clauses().len() < formula.clauses().len());
+
+        let model = Cdcl::new().solve(&simplified, &[]).unwrap();
+        let string = machine.decode(&subject.reconstruct(&model));
+
+        assert_eq!(string.len(), 9);
+        assert_eq!(&string[..3], &[1, 2, 3]);
+
+        for permutation in [[1, 2, 3], [1, 3, 2], [2, 1, 3], [2, 3, 1], [3, 1, 2], [3, 2, 1]] {
+            assert!(string.windows(3).any(|window| window == permutation));
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use itertools::Itertools;
 use std::iter::repeat;
+use std::ops::RangeInclusive;
 
 use crate::literal::Literal;
 use crate::binary::Binary;
@@ -7,6 +8,10 @@ use crate::machine::Machine;
 use crate::goal::Goal;
 use crate::bounds::Bounds;
 use crate::logic::Logic;
+use crate::expression::Expression;
+use crate::constraint::Constraint;
+use crate::problem_config::ProblemConfig;
+use crate::goal_direction::GoalDirection;
 
 pub struct Problem<'a> {
     n: usize,
@@ -15,11 +20,23 @@ pub struct Problem<'a> {
     goal: &'a Goal,
     bounds: &'a Bounds,
     logic: &'a mut Logic<'a>,
+    config: ProblemConfig,
 }
 
 impl<'a> Problem<'a> {
     pub fn new(n: usize, length_of_string: usize, machine: &'a Machine, goal: &'a Goal, bounds: &'a Bounds, logic: &'a mut Logic<'a>) -> Self {
-        Self { n, length_of_string, machine, goal, bounds, logic }
+        let config = ProblemConfig::default();
+
+        Self { n, length_of_string, machine, goal, bounds, logic, config }
+    }
+
+    // The config is recorded in the formula's header so files encoded
+    // differently can be told apart.
+    pub fn set_config(&mut self, config: ProblemConfig) {
+        self.config = config;
+
+        self.logic.set_counter_overflow(config.counter_overflow);
+        self.logic.comment(format!("config {}", config));
     }
 
     pub fn the_machine_starts_in_the_dead_states(&mut self) {
@@ -48,7 +65,22 @@ impl<'a> Problem<'a> {
                     let transition = Logic::and(travel_from, on_symbol);
 
                     self.logic.implies(travel_to, &transition);
-                    self.logic.implies(&transition, travel_to);
+
+                    if self.config.bidirectional_transitions {
+                        self.logic.implies(&transition, travel_to);
+                    }
+                }
+            }
+
+            if self.config.dead_state_transitions {
+                for rank in 2..self.n {
+                    let from_name = Self::dead_state_name(rank - 1);
+                    let to_name = Self::dead_state_name(rank);
+
+                    let travel_from = previous_time.state(&from_name).literals();
+                    let travel_to = current_time.state(&to_name).literals();
+
+                    self.logic.implies(travel_from, travel_to);
                 }
             }
         }
@@ -60,28 +92,156 @@ impl<'a> Problem<'a> {
                 let machine_state = self.machine.at_time(time).state(&name);
                 let goal_state = self.goal.subgoal(&name).state_by_index(time);
 
-                self.logic.implies(&machine_state.literals(), &goal_state.literals());
+                if self.config.goal_direction != GoalDirection::Backward {
+                    self.logic.implies(machine_state.literals(), goal_state.literals());
+                }
+
+                if self.config.goal_direction != GoalDirection::Forward {
+                    self.logic.implies(goal_state.literals(), machine_state.literals());
+                }
             }
         }
     }
 
+    pub fn the_permutation_appears(&mut self, name: &[usize]) {
+        let selectors = self.literals_for_permutation_times(name);
+
+        self.logic.contradiction(&Logic::negate(&selectors));
+    }
+
+    // A literal for each permutation that is only true if it appears in the
+    // string, so the number of true literals is the number of permutations.
+    pub fn literals_for_permutations_appearing(&mut self) -> Vec<Literal> {
+        (1..=self.n).permutations(self.n).map(|name| {
+            let selectors = self.literals_for_permutation_times(&name);
+            let appears = self.logic.new_literal();
+
+            let mut condition = vec![appears];
+            condition.extend(Logic::negate(&selectors));
+
+            self.logic.contradiction(&condition);
+
+            appears
+        }).collect()
+    }
+
     pub fn the_string_starts_with_ascending_numbers(&mut self) {
         let ascending = (1..=self.n).collect::<Vec<_>>();
         self.the_string_starts_with(&ascending);
     }
 
     pub fn the_string_starts_with(&mut self, symbols: &[usize]) {
+        if symbols.len() > self.length_of_string {
+            return self.logic.contradiction(&[]);
+        }
+
         for (time, symbol) in symbols.iter().enumerate() {
             let start_state = self.machine.at_time(time).state(&[*symbol]);
             self.logic.tautology(start_state.literals());
         }
     }
 
+    pub fn the_string_ends_with(&mut self, symbols: &[usize]) {
+        let start = match self.length_of_string.checked_sub(symbols.len()) {
+            Some(time) => time,
+            None => return self.logic.contradiction(&[]),
+        };
+
+        for (offset, symbol) in symbols.iter().enumerate() {
+            self.the_string_has_symbol_at(start + offset, *symbol);
+        }
+    }
+
+    pub fn the_string_has_symbol_at(&mut self, time: usize, symbol: usize) {
+        if time >= self.length_of_string {
+            return self.logic.contradiction(&[]);
+        }
+
+        let state = self.machine.at_time(time).state(&[symbol]);
+        self.logic.tautology(state.literals());
+    }
+
+    // The position of the substring isn't known so it has to start at one of
+    // the places it fits. Logic adds a literal for each of them.
+    pub fn the_string_contains(&mut self, symbols: &[usize]) {
+        let starts = match (self.length_of_string + 1).checked_sub(symbols.len()) {
+            Some(count) => 0..count,
+            None => return self.logic.contradiction(&[]),
+        };
+
+        let windows = starts.map(|start| {
+            Expression::and(symbols.iter().enumerate().flat_map(|(offset, symbol)| {
+                Expression::literals(&self.symbol_literals(start + offset, *symbol))
+            }).collect())
+        }).collect();
+
+        self.logic.assert(&Expression::or(windows));
+    }
+
+    // A string that ends with a permutation can be reversed and relabelled so
+    // that it starts with ascending numbers again. This removes that symmetry
+    // by only allowing the string that is lexically smallest (lex-leader).
+    pub fn the_string_precedes_its_reversal(&mut self) {
+        let last_time = self.length_of_string - 1;
+        let last_rank = self.n - 1;
+
+        let name = Self::dead_state_name(last_rank);
+        let dead_state = self.machine.at_time(last_time).state(&name);
+
+        let mut equal_so_far = self.logic.alias(dead_state.literals()).negate();
+
+        for time in self.n..self.length_of_string {
+            let mirror_time = last_time - time;
+            let next_equal = self.logic.new_literal();
+
+            for symbol in 1..=self.n {
+                for label in 1..=symbol {
+                    // The mirrored symbol is relabelled to 'label' if it is the
+                    // same as the symbol in that position from the end.
+                    let label_time = self.length_of_string - label;
+
+                    for mirror_symbol in 1..=self.n {
+                        let current = self.symbol_literals(time, symbol);
+                        let mirror = self.symbol_literals(mirror_time, mirror_symbol);
+                        let relabel = self.symbol_literals(label_time, mirror_symbol);
+
+                        let condition = Logic::and(&[equal_so_far], &current);
+                        let condition = Logic::and(&condition, &mirror);
+                        let condition = Logic::and(&condition, &relabel);
+
+                        if label < symbol {
+                            self.logic.contradiction(&condition);
+                        } else {
+                            self.logic.implies(&condition, &[next_equal]);
+                        }
+                    }
+                }
+            }
+
+            equal_so_far = next_equal;
+        }
+    }
+
     pub fn the_number_of_wasted_symbols_is_within_bounds(&mut self) {
         let wasted_symbols = self.literals_for_wasted_symbols();
         self.logic.within(&self.bounds, &wasted_symbols);
     }
 
+    pub fn the_number_of_wasted_symbols_is_within_bounds_when(&mut self, activation: Literal) {
+        self.logic.set_activation(Some(activation));
+        self.the_number_of_wasted_symbols_is_within_bounds();
+        self.logic.set_activation(None);
+    }
+
+    // The same bounds as the counters but as pseudo-Boolean constraints on the
+    // wasted symbols up to each index, which PB solvers can reason about
+    // directly. Unlike the counters these also enforce the lower bounds.
+    pub fn constraints_for_wasted_symbols(&mut self) -> Vec<Constraint> {
+        let wasted_symbols = self.literals_for_wasted_symbols();
+
+        Self::constraints_for_ranges(&wasted_symbols, &self.bounds.wasted_symbol_ranges())
+    }
+
     pub fn all_binary_representations_map_to_states(&mut self) {
         for (range, variables) in self.machine.invalid_ranges() {
             for number in range {
@@ -106,6 +266,40 @@ impl<'a> Problem<'a> {
         repeat(0).take(rank + 1).collect()
     }
 
+    fn symbol_literals(&self, time: usize, symbol: usize) -> Vec<Literal> {
+        self.machine.at_time(time).state(&[symbol]).literals().clone()
+    }
+
+    fn constraints_for_ranges(literals: &[Literal], ranges: &[RangeInclusive<usize>]) -> Vec<Constraint> {
+        ranges.iter().enumerate().flat_map(|(index, range)| {
+            let literals = &literals[..=index];
+            let mut constraints = vec![];
+
+            if *range.start() > 0 {
+                constraints.push(Constraint::at_least(literals, *range.start()));
+            }
+
+            if *range.end() < literals.len() {
+                constraints.push(Constraint::at_most(literals, *range.end()));
+            }
+
+            constraints
+        }).collect()
+    }
+
+    fn literals_for_permutation_times(&mut self, name: &[usize]) -> Vec<Literal> {
+        let times = (self.n - 1)..self.length_of_string;
+
+        times.map(|time| {
+            let selector = self.logic.new_literal();
+            let machine_state = self.machine.at_time(time).state(name);
+
+            self.logic.implies(&[selector], machine_state.literals());
+
+            selector
+        }).collect()
+    }
+
     fn literals_for_wasted_symbols(&mut self) -> Vec<Literal> {
         let range = (self.n - 1)..self.length_of_string;
 
@@ -6,6 +6,7 @@ use crate::variable::Variable;
 use crate::state::State;
 use crate::formula::Formula;
 use crate::bounds::Bounds;
+use crate::goal_direction::GoalDirection;
 
 type Subject<'a> = Problem<'a>;
 
@@ -90,6 +91,89 @@ mod the_machine_starts_in_the_dead_states {
     }
 }
 
+mod set_config {
+    use super::*;
+    use crate::cdcl::Cdcl;
+    use crate::backend::Backend;
+
+    fn accepted(config: ProblemConfig) -> Vec<Vec<usize>> {
+        let strings = (0..729).map(|index: usize| {
+            let mut string = vec![1, 2, 3];
+            string.extend((0..6).map(|digit| index / 3_usize.pow(digit) % 3 + 1));
+            string
+        });
+
+        strings.filter(|string| {
+            let mut formula = Formula::new();
+            let machine = Machine::new(3, 9, &mut formula);
+            let goal = Goal::new(3, 9, &mut formula);
+            let bounds = Bounds::new(3, 9, &[3]);
+            let mut logic = Logic::new(&mut formula);
+            let mut subject = Subject::new(3, 9, &machine, &goal, &bounds, &mut logic);
+
+            subject.set_config(config);
+            subject.the_machine_starts_in_the_dead_states();
+            subject.the_machine_changes_state_when_it_reads_input();
+            subject.each_permutation_appears_at_most_once();
+            subject.all_binary_representations_map_to_states();
+            subject.the_number_of_wasted_symbols_is_within_bounds();
+            subject.the_string_starts_with(string);
+
+            Cdcl::new().solve(&formula, &[]).is_some()
+        }).collect()
+    }
+
+    #[test]
+    fn it_accepts_the_same_strings_with_each_sound_choice() {
+        let default = ProblemConfig::default();
+        let expected = accepted(default);
+
+        assert_eq!(expected, vec![vec![1, 2, 3, 1, 2, 1, 3, 2, 1]]);
+
+        assert_eq!(accepted(ProblemConfig { bidirectional_transitions: false, ..default }), expected);
+        assert_eq!(accepted(ProblemConfig { goal_direction: GoalDirection::Both, ..default }), expected);
+        assert_eq!(accepted(ProblemConfig { dead_state_transitions: true, ..default }), expected);
+    }
+
+    // These are why ProblemConfig::is_sound keeps them from extending the table.
+    #[test]
+    fn it_accepts_strings_that_are_not_superpermutations_with_each_unsound_choice() {
+        let default = ProblemConfig::default();
+        let expected = accepted(default);
+
+        for config in [
+            ProblemConfig { goal_direction: GoalDirection::Backward, ..default },
+            ProblemConfig { counter_overflow: false, ..default },
+        ] {
+            let relaxed = accepted(config);
+
+            assert!(relaxed.contains(&expected[0]));
+            assert!(relaxed.len() > expected.len());
+        }
+    }
+
+    #[test]
+    fn it_records_the_config_in_the_formula() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.set_config(ProblemConfig::default());
+
+        let filename = std::env::temp_dir().join("supersat-problem-set-config.dimacs");
+        let filename = filename.to_str().unwrap().to_string();
+
+        formula.write(&filename);
+        let contents = std::fs::read_to_string(&filename).unwrap();
+        std::fs::remove_file(&filename).unwrap();
+
+        assert!(contents.starts_with(&format!("c config {}\np cnf", ProblemConfig::default())));
+    }
+}
+
 mod the_machine_changes_state_when_it_reads_input {
     use super::*;
 
@@ -168,6 +252,149 @@ mod the_machine_changes_state_when_it_reads_input {
             "-1 4 6 0",
         ]);
     }
+
+    #[test]
+    fn it_only_adds_the_clauses_for_one_direction_if_transitions_are_one_way() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.set_config(ProblemConfig { bidirectional_transitions: false, ..ProblemConfig::default() });
+        subject.the_machine_changes_state_when_it_reads_input();
+
+        assert_dimacs(&formula, &[
+            "-1 -5 6 0",
+            "4 -5 6 0",
+            "1 5 -6 0",
+            "-4 5 -6 0",
+            "-4 -8 9 0",
+            "7 -8 9 0",
+            "4 8 -9 0",
+            "-7 8 -9 0",
+        ]);
+    }
+
+    #[test]
+    fn it_adds_clauses_that_dead_states_lead_to_dead_states_if_configured() {
+        let clauses = |dead_state_transitions: bool| {
+            let mut formula = Formula::new();
+            let machine = Machine::new(3, 4, &mut formula);
+            let goal = Goal::new(3, 4, &mut formula);
+            let bounds = Bounds::new(3, 4, &[1]);
+            let mut logic = Logic::new(&mut formula);
+            let mut subject = Subject::new(3, 4, &machine, &goal, &bounds, &mut logic);
+
+            subject.set_config(ProblemConfig { dead_state_transitions, ..ProblemConfig::default() });
+            subject.the_machine_changes_state_when_it_reads_input();
+
+            // One clause for each bit of the dead state in the final rank.
+            let bits = (1..4).map(|time| machine.at_time(time).state(&[0, 0, 0]).literals().len()).sum::<usize>();
+
+            (formula.clauses().len(), bits)
+        };
+
+        let (without, _) = clauses(false);
+        let (with, bits) = clauses(true);
+
+        assert_eq!(with, without + bits);
+    }
+
+    #[test]
+    fn it_uses_fewer_literals_when_conditions_are_shared() {
+        let size = |share_conditions: bool| {
+            let mut formula = Formula::new();
+            let machine = Machine::new(4, 33, &mut formula);
+            let goal = Goal::new(4, 33, &mut formula);
+            let bounds = Bounds::new(4, 33, &[1]);
+            let mut logic = Logic::new(&mut formula);
+            logic.set_share_conditions(share_conditions);
+
+            let mut subject = Subject::new(4, 33, &machine, &goal, &bounds, &mut logic);
+            subject.the_machine_changes_state_when_it_reads_input();
+
+            formula.clauses().iter().map(|c| c.literals().len()).sum::<usize>()
+        };
+
+        assert!(size(true) < size(false));
+    }
+}
+
+mod the_permutation_appears {
+    use super::*;
+
+    #[test]
+    fn it_adds_clauses_that_the_machine_is_in_the_permutations_state_at_some_time() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_permutation_appears(&[2, 1]);
+
+        // Look up the literals for the states so we know what to assert.
+        assert_eq!(literals(machine.at_time(1).state(&[2, 1])), "-5 6");
+        assert_eq!(literals(machine.at_time(2).state(&[2, 1])), "-8 9");
+
+        assert_dimacs(&formula, &[
+            // 14 means the permutation appears at time 1
+            "-5 -14 0",
+            "6 -14 0",
+
+            // 15 means the permutation appears at time 2
+            "-8 -15 0",
+            "9 -15 0",
+
+            // The permutation appears at one of these times
+            "14 15 0",
+        ]);
+    }
+}
+
+mod literals_for_permutations_appearing {
+    use super::*;
+
+    #[test]
+    fn it_returns_a_literal_for_each_permutation_that_implies_it_appears() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        let appearing = subject.literals_for_permutations_appearing();
+
+        assert_eq!(appearing.iter().map(|l| l.to_string()).collect::<Vec<_>>(), &["16", "19"]);
+
+        // Look up the literals for the states so we know what to assert.
+        assert_eq!(literals(machine.at_time(1).state(&[1, 2])), "5 -6");
+        assert_eq!(literals(machine.at_time(2).state(&[1, 2])), "8 -9");
+
+        assert_dimacs(&formula, &[
+            // 14 and 15 mean [1, 2] appears at times 1 and 2
+            "5 -14 0",
+            "-6 -14 0",
+            "8 -15 0",
+            "-9 -15 0",
+
+            // 16 means [1, 2] appears at one of those times
+            "14 15 -16 0",
+
+            // 17 and 18 mean [2, 1] appears at times 1 and 2
+            "-5 -17 0",
+            "6 -17 0",
+            "-8 -18 0",
+            "9 -18 0",
+
+            // 19 means [2, 1] appears at one of those times
+            "17 18 -19 0",
+        ]);
+    }
 }
 
 mod the_string_starts_with_ascending_numbers {
@@ -195,6 +422,129 @@ mod the_string_starts_with_ascending_numbers {
     }
 }
 
+mod the_string_ends_with {
+    use super::*;
+
+    #[test]
+    fn it_adds_tautological_clauses_for_the_end_states() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_string_ends_with(&[2, 1]);
+
+        let time_1_state_2 = machine.at_time(1).state(&[2]);
+        let time_2_state_1 = machine.at_time(2).state(&[1]);
+
+        // Look up the literals for the states so we know what to assert.
+        assert_eq!(literals(time_1_state_2), "4");
+        assert_eq!(literals(time_2_state_1), "-7");
+
+        assert_dimacs(&formula, &["4 0", "-7 0"]);
+    }
+
+    #[test]
+    fn it_adds_an_empty_clause_if_the_symbols_are_longer_than_the_string() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_string_ends_with(&[1, 2, 1, 2]);
+
+        assert_dimacs(&formula, &["0"]);
+    }
+}
+
+mod the_string_has_symbol_at {
+    use super::*;
+
+    #[test]
+    fn it_adds_tautological_clauses_for_the_state_at_that_time() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_string_has_symbol_at(1, 1);
+
+        let time_1_state_1 = machine.at_time(1).state(&[1]);
+        assert_eq!(literals(time_1_state_1), "-4");
+
+        assert_dimacs(&formula, &["-4 0"]);
+    }
+
+    #[test]
+    fn it_adds_an_empty_clause_if_the_time_is_past_the_end_of_the_string() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_string_has_symbol_at(LENGTH, 1);
+
+        assert_dimacs(&formula, &["0"]);
+    }
+}
+
+mod the_string_contains {
+    use super::*;
+
+    #[test]
+    fn it_adds_clauses_that_the_symbols_appear_at_one_of_the_positions() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_string_contains(&[1, 2]);
+
+        // Look up the literals for the states so we know what to assert.
+        assert_eq!(literals(machine.at_time(0).state(&[1])), "-1");
+        assert_eq!(literals(machine.at_time(1).state(&[1])), "-4");
+        assert_eq!(literals(machine.at_time(1).state(&[2])), "4");
+        assert_eq!(literals(machine.at_time(2).state(&[2])), "7");
+
+        assert_dimacs(&formula, &[
+            // 14 means the substring starts at time 0
+            "-1 -14 0",
+            "4 -14 0",
+
+            // 15 means the substring starts at time 1
+            "-4 -15 0",
+            "7 -15 0",
+
+            // The substring starts at one of these times
+            "14 15 0",
+        ]);
+    }
+
+    #[test]
+    fn it_adds_an_empty_clause_if_the_symbols_are_longer_than_the_string() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_string_contains(&[1, 2, 1, 2]);
+
+        assert_dimacs(&formula, &["0"]);
+    }
+}
+
 mod each_permutation_appears_at_most_once {
     use super::*;
 
@@ -253,6 +603,139 @@ mod each_permutation_appears_at_most_once {
             "8 -9 -12 0",
         ]);
     }
+
+    #[test]
+    fn it_adds_clauses_in_the_configured_direction() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.set_config(ProblemConfig { goal_direction: GoalDirection::Backward, ..ProblemConfig::default() });
+        subject.each_permutation_appears_at_most_once();
+
+        assert_dimacs(&formula, &[
+            // G(t=1, n=12) implies S(t=1, n=12)
+            "5 -10 11 0",
+            "-6 -10 11 0",
+
+            // G(t=1, n=21) implies S(t=1, n=21)
+            "-5 -12 13 0",
+            "6 -12 13 0",
+
+            // G(t=2, n=12) implies S(t=2, n=12)
+            "8 10 -11 0",
+            "-9 10 -11 0",
+
+            // G(t=2, n=21) implies S(t=2, n=21)
+            "-8 12 -13 0",
+            "9 12 -13 0",
+        ]);
+    }
+}
+
+mod the_string_precedes_its_reversal {
+    use super::*;
+    use crate::cdcl::Cdcl;
+    use crate::backend::Backend;
+
+    fn solutions(n: usize, length: usize, max_permutations: &[usize], break_symmetry: bool) -> Vec<Vec<usize>> {
+        candidates(n, length, max_permutations).into_iter().filter(|string| {
+            let mut formula = Formula::new();
+            let machine = Machine::new(n, length, &mut formula);
+            let goal = Goal::new(n, length, &mut formula);
+            let bounds = Bounds::new(n, length, max_permutations);
+            let mut logic = Logic::new(&mut formula);
+            let mut subject = Subject::new(n, length, &machine, &goal, &bounds, &mut logic);
+
+            subject.the_machine_starts_in_the_dead_states();
+            subject.the_machine_changes_state_when_it_reads_input();
+            subject.each_permutation_appears_at_most_once();
+            subject.all_binary_representations_map_to_states();
+            subject.the_number_of_wasted_symbols_is_within_bounds();
+            subject.the_string_starts_with(string);
+
+            if break_symmetry {
+                subject.the_string_precedes_its_reversal();
+            }
+
+            Cdcl::new().solve(&formula, &[]).is_some()
+        }).collect()
+    }
+
+    // Strings that start with ascending numbers and contain the number of
+    // permutations the bounds require, without repeating any of them.
+    fn candidates(n: usize, length: usize, max_permutations: &[usize]) -> Vec<Vec<usize>> {
+        let mut strings = vec![(1..=n).collect::<Vec<_>>()];
+
+        for _ in n..length {
+            strings = strings.iter().flat_map(|string| {
+                (1..=n).map(move |symbol| {
+                    let mut string = string.clone();
+                    string.push(symbol);
+                    string
+                })
+            }).filter(|string| {
+                let permutations = permutations(string, n);
+                permutations.len() == permutations.iter().collect::<HashSet<_>>().len()
+            }).collect();
+        }
+
+        let goal_permutations = length - (n - 1) - max_permutations.len();
+
+        strings.into_iter()
+            .filter(|string| permutations(string, n).len() == goal_permutations)
+            .collect()
+    }
+
+    fn permutations(string: &[usize], n: usize) -> Vec<&[usize]> {
+        string.windows(n)
+            .filter(|window| window.iter().collect::<HashSet<_>>().len() == n)
+            .collect()
+    }
+
+    fn ends_with_permutation(string: &[usize], n: usize) -> bool {
+        string.iter().rev().take(n).collect::<HashSet<_>>().len() == n
+    }
+
+    fn reverse_and_relabel(string: &[usize], n: usize) -> Vec<usize> {
+        let labels = string.iter().rev().take(n).cloned().collect::<Vec<_>>();
+
+        string.iter().rev()
+            .map(|symbol| labels.iter().position(|l| l == symbol).unwrap() + 1)
+            .collect()
+    }
+
+    fn assert_symmetry_is_broken(n: usize, length: usize, max_permutations: &[usize]) {
+        let all = solutions(n, length, max_permutations, false);
+        let broken = solutions(n, length, max_permutations, true);
+
+        let expected = all.iter()
+            .filter(|s| !ends_with_permutation(s, n) || **s <= reverse_and_relabel(s, n))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(broken, expected);
+
+        // Each pair of symmetric strings is reduced to one of them.
+        let symmetric = all.iter().filter(|s| ends_with_permutation(s, n)).count();
+        let palindromes = all.iter().filter(|s| **s == reverse_and_relabel(s, n)).count();
+
+        assert!(symmetric > palindromes);
+        assert_eq!(broken.len(), all.len() - (symmetric - palindromes) / 2);
+    }
+
+    #[test]
+    fn it_removes_strings_that_are_lexically_greater_than_their_reversal_for_n_equals_3() {
+        assert_symmetry_is_broken(3, 8, &[3]);
+    }
+
+    #[test]
+    fn it_removes_strings_that_are_lexically_greater_than_their_reversal_for_n_equals_4() {
+        assert_symmetry_is_broken(4, 10, &[4]);
+    }
 }
 
 mod the_number_of_wasted_symbols_is_within_bounds {
@@ -308,6 +791,77 @@ mod the_number_of_wasted_symbols_is_within_bounds {
     }
 }
 
+mod the_number_of_wasted_symbols_is_within_bounds_when {
+    use super::*;
+
+    #[test]
+    fn it_only_limits_the_number_of_wasted_symbols_when_the_activation_literal_is_true() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let activation = Literal::positive(Variable::new(99));
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        subject.the_number_of_wasted_symbols_is_within_bounds_when(activation);
+        subject.the_machine_starts_in_the_dead_states();
+
+        // The same clauses as above but each one is guarded by -99.
+        assert_dimacs(&formula, &[
+            "5 6 14 -99 0",
+            "-5 -14 -99 0",
+            "-6 -14 -99 0",
+            "8 9 15 -99 0",
+            "-8 -15 -99 0",
+            "-9 -15 -99 0",
+            "-14 16 -99 0",
+            "-15 17 -99 0",
+            "-16 17 -99 0",
+            "-15 -16 -99 0",
+
+            // Clauses added afterwards aren't guarded.
+            "-2 0",
+            "-3 0",
+        ]);
+    }
+}
+
+mod constraints_for_wasted_symbols {
+    use super::*;
+
+    #[test]
+    fn it_bounds_the_wasted_symbols_up_to_each_index_from_both_sides() {
+        let mut formula = Formula::new();
+        let machine = Machine::new(N, LENGTH, &mut formula);
+        let goal = Goal::new(N, LENGTH, &mut formula);
+        let bounds = Bounds::new(N, LENGTH, &[1]);
+        let mut logic = Logic::new(&mut formula);
+        let mut subject = Subject::new(N, LENGTH, &machine, &goal, &bounds, &mut logic);
+
+        let constraints = subject.constraints_for_wasted_symbols()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+
+        // 14 and 15 are aliases for S(t=1, n=00) and S(t=2, n=00), and the
+        // first index allows 0..=1 wasted symbols so it isn't constrained.
+        assert_eq!(constraints, &[
+            "+1 x14 +1 x15 >= 1 ;",
+            "+1 ~x14 +1 ~x15 >= 1 ;",
+        ]);
+
+        assert_dimacs(&formula, &[
+            "5 6 14 0",
+            "-5 -14 0",
+            "-6 -14 0",
+            "8 9 15 0",
+            "-8 -15 0",
+            "-9 -15 0",
+        ]);
+    }
+}
+
 mod all_binary_representations_map_to_states {
     use super::*;
 
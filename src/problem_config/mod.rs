@@ -0,0 +1,70 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::goal_direction::GoalDirection;
+
+// Choices in how the problem is encoded, so they can be compared against each
+// other. The defaults are the encoding described in the README.
+//
+// - bidirectional_transitions: also imply the next state from the previous
+//   state and the symbol, which isn't strictly needed
+// - goal_direction: see GoalDirection
+// - dead_state_transitions: imply the next rank's dead state from the previous
+//   one directly, rather than only through the bans on invalid numbers
+// - counter_overflow: add clause (5) of the sequential counter, without which
+//   the upper bounds on wasted symbols aren't enforced
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProblemConfig {
+    pub bidirectional_transitions: bool,
+    pub goal_direction: GoalDirection,
+    pub dead_state_transitions: bool,
+    pub counter_overflow: bool,
+}
+
+impl Default for ProblemConfig {
+    fn default() -> Self {
+        Self {
+            bidirectional_transitions: true,
+            goal_direction: GoalDirection::Forward,
+            dead_state_transitions: false,
+            counter_overflow: true,
+        }
+    }
+}
+
+impl ProblemConfig {
+    // Backward goals and no counter overflow clauses relax the problem so
+    // they can find strings that aren't superpermutations.
+    pub fn is_sound(&self) -> bool {
+        self.goal_direction != GoalDirection::Backward && self.counter_overflow
+    }
+
+    // This config and each sound choice changed in turn, which encode the same
+    // problem differently so a portfolio can race them.
+    pub fn alternatives(&self) -> Vec<Self> {
+        let mut alternatives = vec![
+            *self,
+            Self { bidirectional_transitions: !self.bidirectional_transitions, ..*self },
+            Self { dead_state_transitions: !self.dead_state_transitions, ..*self },
+        ];
+
+        match self.goal_direction {
+            GoalDirection::Forward => alternatives.push(Self { goal_direction: GoalDirection::Both, ..*self }),
+            GoalDirection::Both => alternatives.push(Self { goal_direction: GoalDirection::Forward, ..*self }),
+            GoalDirection::Backward => {},
+        }
+
+        alternatives
+    }
+}
+
+impl Display for ProblemConfig {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "bidirectional-transitions={} ", self.bidirectional_transitions)?;
+        write!(f, "goal-direction={} ", self.goal_direction)?;
+        write!(f, "dead-state-transitions={} ", self.dead_state_transitions)?;
+        write!(f, "counter-overflow={}", self.counter_overflow)
+    }
+}
+
+#[cfg(test)]
+mod test;
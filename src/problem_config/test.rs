@@ -0,0 +1,83 @@
+use super::*;
+
+type Subject = ProblemConfig;
+
+mod default {
+    use super::*;
+
+    #[test]
+    fn it_is_the_encoding_described_in_the_readme() {
+        let subject = Subject::default();
+
+        assert!(subject.bidirectional_transitions);
+        assert_eq!(subject.goal_direction, GoalDirection::Forward);
+        assert!(!subject.dead_state_transitions);
+        assert!(subject.counter_overflow);
+    }
+}
+
+mod is_sound {
+    use super::*;
+
+    #[test]
+    fn it_is_false_for_the_choices_that_relax_the_problem() {
+        let backward = Subject { goal_direction: GoalDirection::Backward, ..Subject::default() };
+        let no_counter_overflow = Subject { counter_overflow: false, ..Subject::default() };
+
+        assert!(!backward.is_sound());
+        assert!(!no_counter_overflow.is_sound());
+    }
+
+    #[test]
+    fn it_is_true_for_the_other_choices() {
+        let both = Subject { goal_direction: GoalDirection::Both, ..Subject::default() };
+        let one_way = Subject { bidirectional_transitions: false, dead_state_transitions: true, ..Subject::default() };
+
+        assert!(Subject::default().is_sound());
+        assert!(both.is_sound());
+        assert!(one_way.is_sound());
+    }
+}
+
+mod alternatives {
+    use super::*;
+
+    #[test]
+    fn it_changes_each_sound_choice_in_turn() {
+        let subject = Subject::default();
+
+        assert_eq!(subject.alternatives(), vec![
+            subject,
+            Subject { bidirectional_transitions: false, ..subject },
+            Subject { dead_state_transitions: true, ..subject },
+            Subject { goal_direction: GoalDirection::Both, ..subject },
+        ]);
+    }
+
+    #[test]
+    fn it_keeps_a_backward_goal_since_the_other_directions_answer_differently() {
+        let subject = Subject { goal_direction: GoalDirection::Backward, ..Subject::default() };
+
+        assert!(subject.alternatives().iter().all(|config| config.goal_direction == GoalDirection::Backward));
+        assert_eq!(subject.alternatives().len(), 3);
+    }
+}
+
+mod display {
+    use super::*;
+
+    #[test]
+    fn it_formats_each_choice_as_a_key_and_value() {
+        let subject = Subject {
+            bidirectional_transitions: false,
+            goal_direction: GoalDirection::Both,
+            dead_state_transitions: true,
+            counter_overflow: true,
+        };
+
+        assert_eq!(
+            format!("{}", subject),
+            "bidirectional-transitions=false goal-direction=both dead-state-transitions=true counter-overflow=true",
+        );
+    }
+}
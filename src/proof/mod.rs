@@ -0,0 +1,73 @@
+use std::process::Command;
+
+use std::io::{BufRead, BufReader};
+use std::fs::{metadata, File};
+use std::path::Path;
+
+// Checks a DRAT or LRAT proof that a formula is unsatisfiable with an external
+// checker, such as drat-trim or cake_lpr, that takes the formula and the proof
+// as its arguments.
+pub struct Proof {
+
+}
+
+impl Proof {
+    pub fn check(command: &str, input: &String, proof: &String) -> bool {
+        let filename = Self::filename(command, proof);
+
+        if Self::stale(&filename, proof) {
+            let output = File::create(&filename).unwrap();
+
+            Command::new(command)
+                .arg(input)
+                .arg(proof)
+                .stdout(output)
+                .spawn()
+                .unwrap()
+                .wait()
+                .unwrap();
+        }
+
+        let output = File::open(&filename).unwrap();
+        let lines = BufReader::new(output).lines().map(|l| l.unwrap());
+
+        match Self::parse(lines) {
+            Some(verified) => verified,
+            None => panic!("Failed to parse {}", filename),
+        }
+    }
+
+    fn filename(command: &str, proof: &str) -> String {
+        let command = Path::new(command).file_name().unwrap().to_str().unwrap();
+
+        format!("{}.{}.output", proof, command)
+    }
+
+    // The verdict is for the proof as it was, so it's checked again if the
+    // proof has been written since, e.g. by --builtin --proof.
+    fn stale(filename: &String, proof: &String) -> bool {
+        let modified = |path: &String| metadata(path).and_then(|m| m.modified()).ok();
+
+        match (modified(filename), modified(proof)) {
+            (Some(checked), Some(written)) => checked < written,
+            _ => true,
+        }
+    }
+
+    // drat-trim prints 's VERIFIED' or 's NOT VERIFIED' and cake_lpr prints
+    // 's VERIFIED UNSAT'.
+    fn parse<I: Iterator<Item=String>>(lines: I) -> Option<bool> {
+        for line in lines {
+            if line.starts_with("s VERIFIED") {
+                return Some(true);
+            } else if line.starts_with("s NOT VERIFIED") {
+                return Some(false);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,78 @@
+use super::*;
+
+type Subject = Proof;
+
+fn lines(text: &str) -> impl Iterator<Item=String> + '_ {
+    text.lines().map(String::from)
+}
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn it_accepts_a_proof_that_drat_trim_verified() {
+        let output = "c parsing input formula\nc verifying\ns VERIFIED\n";
+
+        assert_eq!(Subject::parse(lines(output)), Some(true));
+    }
+
+    #[test]
+    fn it_accepts_a_proof_that_cake_lpr_verified() {
+        assert_eq!(Subject::parse(lines("s VERIFIED UNSAT\n")), Some(true));
+    }
+
+    #[test]
+    fn it_rejects_a_proof_that_failed_to_verify() {
+        let output = "c ERROR: lemma is not RUP\ns NOT VERIFIED\n";
+
+        assert_eq!(Subject::parse(lines(output)), Some(false));
+    }
+
+    #[test]
+    fn it_returns_none_if_the_checker_did_not_finish() {
+        assert_eq!(Subject::parse(lines("c parsing input formula\n")), None);
+    }
+}
+
+mod filename {
+    use super::*;
+
+    #[test]
+    fn it_names_the_output_after_the_checker_without_its_directory() {
+        assert_eq!(Subject::filename("drat-trim", "scratch/x.drat"), "scratch/x.drat.drat-trim.output");
+        assert_eq!(Subject::filename("/opt/bin/drat-trim", "scratch/x.drat"), "scratch/x.drat.drat-trim.output");
+    }
+}
+
+mod check {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{remove_file, write, set_permissions, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn it_checks_the_proof_again_once_it_has_been_rewritten() {
+        let path = |name: &str| temp_dir().join(format!("supersat-proof-{}", name)).to_str().unwrap().to_string();
+        let (checker, input, proof) = (path("checker.sh"), path("formula.dimacs"), path("formula.drat"));
+
+        // The checker only verifies proofs that end with the empty clause.
+        write(&checker, "#!/bin/sh\nif grep -qx 0 \"$2\"; then echo 's VERIFIED'; else echo 's NOT VERIFIED'; fi\n").unwrap();
+        set_permissions(&checker, Permissions::from_mode(0o755)).unwrap();
+        write(&input, "p cnf 1 2\n1 0\n-1 0\n").unwrap();
+
+        write(&proof, "1 0\n").unwrap();
+        assert!(!Subject::check(&checker, &input, &proof));
+
+        sleep(Duration::from_millis(10));
+        write(&proof, "0\n").unwrap();
+        assert!(Subject::check(&checker, &input, &proof));
+
+        remove_file(Subject::filename(&checker, &proof)).unwrap();
+
+        for filename in [checker, input, proof] {
+            remove_file(filename).unwrap();
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ProofError {
+    LemmaNotImplied { line: usize, lemma: Vec<isize> },
+    EmptyClauseNotDerived,
+}
+
+impl Display for ProofError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::LemmaNotImplied { line, lemma } => {
+                let numbers = lemma.iter().map(|n| n.to_string()).chain(Some("0".to_string()));
+                write!(f, "The lemma on line {} isn't implied: {}", line, numbers.collect::<Vec<_>>().join(" "))
+            },
+
+            Self::EmptyClauseNotDerived =>
+                write!(f, "The proof ends without deriving the empty clause"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,19 @@
+use super::*;
+
+type Subject = ProofError;
+
+mod display {
+    use super::*;
+
+    #[test]
+    fn it_reports_the_first_lemma_that_failed() {
+        let subject = Subject::LemmaNotImplied { line: 12, lemma: vec![1, -3] };
+        assert_eq!(subject.to_string(), "The lemma on line 12 isn't implied: 1 -3 0");
+    }
+
+    #[test]
+    fn it_explains_that_the_proof_is_incomplete() {
+        let subject = Subject::EmptyClauseNotDerived;
+        assert_eq!(subject.to_string(), "The proof ends without deriving the empty clause");
+    }
+}
@@ -25,6 +25,10 @@ impl Rank {
         Self { variables, states }
     }
 
+    pub fn variables(&self) -> &Vec<Variable> {
+        &self.variables
+    }
+
     pub fn state(&self, name: &[usize], n: usize) -> &State {
         self.state_by_index(State::index(name, n))
     }
@@ -39,6 +39,18 @@ mod new {
     }
 }
 
+mod variables {
+    use super::*;
+
+    #[test]
+    fn it_returns_a_reference_to_the_variables_shared_by_the_states() {
+        let mut formula = Formula::new();
+        let subject = Subject::new(50, &mut formula);
+
+        assert_eq!(subject.variables(), &subject.variables);
+    }
+}
+
 mod state {
     use super::*;
 
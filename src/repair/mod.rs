@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::formula::Formula;
+use crate::machine::Machine;
+use crate::goal::Goal;
+use crate::bounds::Bounds;
+use crate::logic::Logic;
+use crate::problem::Problem;
+use crate::problem_config::ProblemConfig;
+use crate::solver::Solver;
+
+pub struct Repair {
+    n: usize,
+    string: Vec<usize>,
+    config: ProblemConfig,
+}
+
+impl Repair {
+    pub fn new(n: usize, string: &[usize]) -> Self {
+        let string = string.to_vec();
+
+        Self { n, string, config: ProblemConfig::default() }
+    }
+
+    pub fn set_config(&mut self, config: ProblemConfig) {
+        self.config = config;
+    }
+
+    pub fn string(&self) -> &Vec<usize> {
+        &self.string
+    }
+
+    pub fn improve(&mut self, size: usize) {
+        let mut start = 0;
+
+        while start + size <= self.string.len() {
+            println!("Trying to shorten the window at {}..{}...", start, start + size);
+
+            if let Some(replacement) = self.shorten(start, size) {
+                self.string = self.splice(start, size, &replacement);
+                println!("Shortened the string to length {}.", self.string.len());
+            }
+
+            start += 1;
+        }
+    }
+
+    pub fn shorten(&self, start: usize, size: usize) -> Option<Vec<usize>> {
+        let (before, after) = self.context(start, size);
+        let minimum_size = self.n.saturating_sub(before.len() + after.len());
+
+        let mut shortest = None;
+
+        for replacement_size in (minimum_size..size).rev() {
+            let filename = self.filename(start, size, replacement_size);
+            let length_of_string = before.len() + replacement_size + after.len();
+
+            self.generate(&filename, start, size, replacement_size);
+
+            let model = match Solver::model(&filename) {
+                Some(model) => model,
+                None => break,
+            };
+
+            // Variables are numbered in the order they're created so a new
+            // machine can decode the model, even if the formula was cached.
+            let machine = Machine::new(self.n, length_of_string, &mut Formula::new());
+            let string = machine.decode(&model);
+
+            let replacement = &string[before.len()..(before.len() + replacement_size)];
+            shortest = Some(replacement.to_vec());
+        }
+
+        shortest
+    }
+
+    fn generate(&self, filename: &String, start: usize, size: usize, replacement_size: usize) {
+        let n = self.n;
+        let (before, after) = self.context(start, size);
+        let permutations = self.required_permutations(start, size);
+        let length_of_string = before.len() + replacement_size + after.len();
+        let config = self.config;
+
+        Formula::generate(filename, |formula| {
+            println!("Generating {}...", filename);
+
+            let machine = Machine::new(n, length_of_string, formula);
+            let goal = Goal::new(n, length_of_string, formula);
+            let bounds = Bounds::new(n, length_of_string, &[]);
+            let mut logic = Logic::new(formula);
+            let mut problem = Problem::new(n, length_of_string, &machine, &goal, &bounds, &mut logic);
+            problem.set_config(config);
+
+            problem.the_machine_starts_in_the_dead_states();
+            problem.the_machine_changes_state_when_it_reads_input();
+            problem.all_binary_representations_map_to_states();
+
+            // The symbols either side of the window fix the machine's states at
+            // its edges so only the window itself needs to be solved.
+            problem.the_string_starts_with(&before);
+            problem.the_string_ends_with(&after);
+
+            for name in &permutations {
+                problem.the_permutation_appears(name);
+            }
+        });
+    }
+
+    fn context(&self, start: usize, size: usize) -> (Vec<usize>, Vec<usize>) {
+        let end = start + size;
+
+        let before_start = start.saturating_sub(self.n - 1);
+        let after_end = (end + self.n - 1).min(self.string.len());
+
+        let before = self.string[before_start..start].to_vec();
+        let after = self.string[end..after_end].to_vec();
+
+        (before, after)
+    }
+
+    // Permutations that only appear in places overlapping the window need to
+    // appear in its replacement. The rest of the string covers the others.
+    fn required_permutations(&self, start: usize, size: usize) -> Vec<Vec<usize>> {
+        let mut inside = HashSet::new();
+        let mut outside = HashSet::new();
+
+        for (index, window) in self.string.windows(self.n).enumerate() {
+            if window.iter().collect::<HashSet<_>>().len() != self.n {
+                continue;
+            }
+
+            if index < start + size && index + self.n > start {
+                inside.insert(window.to_vec());
+            } else {
+                outside.insert(window.to_vec());
+            }
+        }
+
+        let mut permutations = inside.difference(&outside).cloned().collect::<Vec<_>>();
+        permutations.sort();
+
+        permutations
+    }
+
+    fn splice(&self, start: usize, size: usize, replacement: &[usize]) -> Vec<usize> {
+        let mut string = self.string.clone();
+        string.splice(start..(start + size), replacement.iter().cloned());
+
+        string
+    }
+
+    fn filename(&self, start: usize, size: usize, replacement_size: usize) -> String {
+        // Formulas are cached by filename so the config needs to be part of it.
+        let mut hasher = DefaultHasher::new();
+        self.string.hash(&mut hasher);
+        self.config.hash(&mut hasher);
+
+        format!(
+            "scratch/{}-symbols-repair-{:x}-{}-{}-to-{}.dimacs",
+            self.n, hasher.finish(), start, size, replacement_size,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test;
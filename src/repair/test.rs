@@ -0,0 +1,98 @@
+use super::*;
+
+type Subject = Repair;
+
+// The minimal superpermutation for n=3 with a wasted symbol on the end.
+const STRING: &[usize] = &[1, 2, 3, 1, 2, 1, 3, 2, 1, 3];
+
+mod new {
+    use super::*;
+
+    #[test]
+    fn it_builds_the_struct() {
+        let subject = Subject::new(3, STRING);
+
+        assert_eq!(subject.n, 3);
+        assert_eq!(subject.string(), STRING);
+    }
+}
+
+mod context {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_n_minus_one_symbols_either_side_of_the_window() {
+        let subject = Subject::new(3, STRING);
+
+        assert_eq!(subject.context(4, 3), (vec![3, 1], vec![2, 1]));
+    }
+
+    #[test]
+    fn it_returns_fewer_symbols_at_the_edges_of_the_string() {
+        let subject = Subject::new(3, STRING);
+
+        assert_eq!(subject.context(1, 3), (vec![1], vec![2, 1]));
+        assert_eq!(subject.context(8, 2), (vec![3, 2], vec![]));
+    }
+}
+
+mod required_permutations {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_permutations_that_only_appear_in_places_overlapping_the_window() {
+        let subject = Subject::new(3, STRING);
+
+        // 213 also appears at the end of the string so it isn't required.
+        assert_eq!(subject.required_permutations(4, 3), &[
+            vec![1, 3, 2],
+            vec![3, 1, 2],
+            vec![3, 2, 1],
+        ]);
+    }
+
+    #[test]
+    fn it_does_not_require_permutations_that_also_appear_elsewhere() {
+        let subject = Subject::new(3, &[1, 2, 3, 1, 2, 3]);
+
+        assert_eq!(subject.required_permutations(0, 2), &[vec![2, 3, 1]]);
+    }
+}
+
+mod splice {
+    use super::*;
+
+    #[test]
+    fn it_replaces_the_window_with_the_replacement() {
+        let subject = Subject::new(3, STRING);
+
+        assert_eq!(subject.splice(8, 2, &[1]), &[1, 2, 3, 1, 2, 1, 3, 2, 1]);
+    }
+}
+
+mod filename {
+    use super::*;
+
+    #[test]
+    fn it_includes_the_window_and_the_size_of_its_replacement() {
+        let subject = Subject::new(3, STRING);
+        let filename = subject.filename(4, 3, 2);
+
+        assert!(filename.starts_with("scratch/3-symbols-repair-"));
+        assert!(filename.ends_with("-4-3-to-2.dimacs"));
+    }
+}
+
+mod set_config {
+    use super::*;
+
+    #[test]
+    fn it_caches_the_formulas_for_each_config_separately() {
+        let mut subject = Subject::new(3, STRING);
+        let before = subject.filename(4, 3, 2);
+
+        subject.set_config(ProblemConfig { bidirectional_transitions: false, ..ProblemConfig::default() });
+
+        assert_ne!(subject.filename(4, 3, 2), before);
+    }
+}
@@ -0,0 +1,11 @@
+// Decides which numbers of permutations to ask about when filling in a row of
+// max_permutations. The row is known to be at least lower and at most upper,
+// and satisfiable answers whether a string with that many permutations exists.
+// Smaller numbers are always satisfiable if larger ones are so strategies can
+// skip the queries they don't need.
+pub trait SearchStrategy {
+    fn search(&self, lower: usize, upper: usize, satisfiable: &mut dyn FnMut(usize) -> bool) -> usize;
+}
+
+#[cfg(test)]
+pub mod test;
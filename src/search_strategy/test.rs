@@ -0,0 +1,50 @@
+use super::*;
+use crate::known::Known;
+use crate::utility::Utility;
+use crate::descending::Descending;
+use crate::binary_search::BinarySearch;
+use crate::galloping::Galloping;
+
+// Searches with a fake solver that finds a string whenever there are no more
+// than answer permutations and returns the row along with the queries made.
+pub fn search(subject: &dyn SearchStrategy, lower: usize, upper: usize, answer: usize) -> (usize, Vec<usize>) {
+    let mut queries = vec![];
+
+    let row = subject.search(lower, upper, &mut |permutations| {
+        queries.push(permutations);
+        permutations <= answer
+    });
+
+    (row, queries)
+}
+
+fn strategies() -> Vec<Box<dyn SearchStrategy>> {
+    vec![Box::new(Descending), Box::new(BinarySearch), Box::new(Galloping)]
+}
+
+mod search {
+    use super::*;
+
+    #[test]
+    fn it_finds_every_row_of_the_known_tables() {
+        for subject in strategies() {
+            for n in 2..=6 {
+                let table = Known::table(n).unwrap().max_permutations;
+
+                for (row, &answer) in table.iter().enumerate() {
+                    let lower = if row == 0 { 0 } else { table[row - 1] };
+                    let upper = (lower + n).min(Utility::factorial(n));
+
+                    assert_eq!(search(subject.as_ref(), lower, upper, answer).0, answer);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_does_not_query_when_the_bounds_are_equal() {
+        for subject in strategies() {
+            assert_eq!(search(subject.as_ref(), 5, 5, 5), (5, vec![]));
+        }
+    }
+}
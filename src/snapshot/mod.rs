@@ -4,6 +4,7 @@ use crate::variable::Variable;
 use crate::formula::Formula;
 use crate::state::State;
 use crate::rank::Rank;
+use crate::model::Model;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Snapshot {
@@ -26,6 +27,20 @@ impl Snapshot {
         rank.state(name, n)
     }
 
+    pub fn symbol_variables(&self) -> &Vec<Variable> {
+        self.ranks[0].variables()
+    }
+
+    pub fn symbol(&self, model: &Model) -> usize {
+        let n = self.ranks.len();
+        let symbol = (1..=n).find(|s| model.satisfies(self.state(&[*s]).literals()));
+
+        match symbol {
+            Some(symbol) => symbol,
+            None => panic!("The model doesn't contain a symbol for the snapshot."),
+        }
+    }
+
     pub fn invalid_ranges(&self) -> Vec<(Range<usize>, &Vec<Variable>)> {
         self.ranks.iter().map(|r| r.invalid_range()).collect()
     }
@@ -32,6 +32,43 @@ mod state {
     }
 }
 
+mod symbol_variables {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_variables_of_the_first_rank() {
+        let mut formula = Formula::new();
+        let subject = Subject::new(N, &mut formula);
+
+        assert_eq!(subject.symbol_variables(), subject.ranks[0].variables());
+        assert_eq!(subject.symbol_variables().len(), 2);
+    }
+}
+
+mod symbol {
+    use super::*;
+    use crate::model::Model;
+
+    #[test]
+    fn it_returns_the_symbol_whose_state_is_true_in_the_model() {
+        let mut formula = Formula::new();
+        let subject = Subject::new(N, &mut formula);
+
+        let model = Model::new(subject.state(&[3]).literals());
+
+        assert_eq!(subject.symbol(&model), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "The model doesn't contain a symbol for the snapshot.")]
+    fn it_panics_if_none_of_the_symbols_are_true_in_the_model() {
+        let mut formula = Formula::new();
+        let subject = Subject::new(N, &mut formula);
+
+        subject.symbol(&Model::new(&[]));
+    }
+}
+
 mod invalid_ranges {
     use super::*;
 
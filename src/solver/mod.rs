@@ -1,41 +1,119 @@
 use std::process::Command;
 
-use std::io::{BufRead, BufReader};
-use std::fs::File;
+use std::io::{copy, BufRead, BufReader};
+use std::process::Stdio;
 use std::path::Path;
 
+use crate::literal::Literal;
+use crate::model::Model;
+use crate::compression::Compression;
+
 pub struct Solver {
 
 }
 
 impl Solver {
     pub fn solve(input: &String) -> bool {
-        let filename = format!("{}.output", input);
+        Self::model(input).is_some()
+    }
+
+    pub fn model(input: &String) -> Option<Model> {
+        Self::model_with("lingeling", input)
+    }
+
+    pub fn model_with(command: &str, input: &String) -> Option<Model> {
+        let filename = Compression::append(input, ".output");
 
         if !Path::new(&filename).exists() {
-            let output = File::create(&filename).unwrap();
-
-            Command::new("lingeling")
-                .arg(input)
-                .stdout(output)
-                .spawn()
-                .unwrap()
-                .wait()
-                .unwrap();
+            Self::run(command, input, &filename);
+        }
+
+        Self::read(&filename)
+    }
+
+    // The proof is written next to the output by solvers that take its
+    // filename as their second argument, such as cadical and kissat. Their
+    // output is cached separately from runs without a proof, and an
+    // unsatisfiable answer is only reused while its proof is still there.
+    pub fn model_with_proof(command: &str, input: &String, proof: &String) -> Option<Model> {
+        let filename = Compression::append(input, ".proof.output");
+
+        let cached = match Self::answer(&filename) {
+            Some(Some(_)) => true,
+            Some(None) => Path::new(proof).exists(),
+            None => false,
+        };
+
+        if !cached {
+            Self::run_with_args(command, &[input, proof], &filename);
+        }
+
+        Self::read(&filename)
+    }
+
+    pub fn run(command: &str, input: &String, filename: &String) {
+        Self::run_with_args(command, &[input], filename);
+    }
+
+    // The output is compressed if its filename asks for it.
+    fn run_with_args(command: &str, args: &[&String], filename: &String) {
+        let mut output = Compression::create(filename);
+
+        let mut child = Command::new(command)
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        copy(child.stdout.as_mut().unwrap(), &mut output).unwrap();
+        child.wait().unwrap();
+    }
+
+    pub fn read(filename: &String) -> Option<Model> {
+        match Self::answer(filename) {
+            Some(model) => model,
+            None => panic!("Failed to parse {}", filename),
         }
+    }
 
-        let output = File::open(&filename).unwrap();
+    // Returns None rather than panicking if the solver didn't finish.
+    pub fn answer(filename: &String) -> Option<Option<Model>> {
+        if !Path::new(filename).exists() {
+            return None;
+        }
 
-        for line in BufReader::new(output).lines() {
-            let line = line.unwrap();
+        let output = Compression::open(filename);
+        let lines = BufReader::new(output).lines().map_while(Result::ok);
 
+        Self::parse(lines)
+    }
+
+    fn parse<I: Iterator<Item=String>>(lines: I) -> Option<Option<Model>> {
+        let mut satisfiable = false;
+        let mut literals = vec![];
+
+        for line in lines {
             if line.contains("s SATISFIABLE") {
-                return true;
+                satisfiable = true;
             } else if line.contains("s UNSATISFIABLE") {
-                return false;
+                return Some(None);
+            } else if line.starts_with("v ") {
+                // Pseudo-Boolean solvers write literals as x1 and -x1.
+                let numbers = line.split_whitespace().skip(1)
+                    .map(|n| n.replacen('x', "", 1).parse::<isize>().unwrap())
+                    .filter(|n| *n != 0);
+
+                literals.extend(numbers.map(Literal::from_dimacs));
             }
         }
 
-        panic!("Failed to parse {}", filename);
+        if !satisfiable {
+            return None;
+        }
+
+        Some(Some(Model::new(&literals)))
     }
 }
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,84 @@
+use super::*;
+
+type Subject = Solver;
+
+fn lines(text: &str) -> impl Iterator<Item=String> + '_ {
+    text.lines().map(String::from)
+}
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_model_if_the_formula_is_satisfiable() {
+        let output = "c lingeling\ns SATISFIABLE\nv 1 -2\nv 3 0\n";
+        let expected = Model::new(&[
+            Literal::from_dimacs(1),
+            Literal::from_dimacs(-2),
+            Literal::from_dimacs(3),
+        ]);
+
+        assert_eq!(Subject::parse(lines(output)), Some(Some(expected)));
+    }
+
+    #[test]
+    fn it_parses_the_model_from_a_pseudo_boolean_solver() {
+        let output = "c roundingsat\ns SATISFIABLE\nv x1 -x2 x3\n";
+        let expected = Model::new(&[
+            Literal::from_dimacs(1),
+            Literal::from_dimacs(-2),
+            Literal::from_dimacs(3),
+        ]);
+
+        assert_eq!(Subject::parse(lines(output)), Some(Some(expected)));
+    }
+
+    #[test]
+    fn it_returns_no_model_if_the_formula_is_unsatisfiable() {
+        let output = "c lingeling\ns UNSATISFIABLE\n";
+
+        assert_eq!(Subject::parse(lines(output)), Some(None));
+    }
+
+    #[test]
+    fn it_returns_none_if_there_is_no_result() {
+        assert_eq!(Subject::parse(lines("c killed\n")), None);
+    }
+}
+
+mod model_with_proof {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{read_to_string, remove_file, write, set_permissions, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn it_runs_the_solver_again_if_the_proof_is_missing() {
+        let path = |name: &str| temp_dir().join(format!("supersat-solver-{}", name)).to_str().unwrap().to_string();
+        let (solver, input, proof) = (path("solver.sh"), path("formula.dimacs"), path("formula.drat"));
+
+        // The solver stamps each proof with the time it was written.
+        write(&solver, "#!/bin/sh\necho \"c run $(date +%N)\" > \"$2\"\necho 0 >> \"$2\"\necho 's UNSATISFIABLE'\n").unwrap();
+        set_permissions(&solver, Permissions::from_mode(0o755)).unwrap();
+        write(&input, "p cnf 1 2\n1 0\n-1 0\n").unwrap();
+
+        assert_eq!(Subject::model_with_proof(&solver, &input, &proof), None);
+        let first = read_to_string(&proof).unwrap();
+
+        // The answer is reused while the proof is there.
+        assert_eq!(Subject::model_with_proof(&solver, &input, &proof), None);
+        assert_eq!(read_to_string(&proof).unwrap(), first);
+
+        remove_file(&proof).unwrap();
+
+        assert_eq!(Subject::model_with_proof(&solver, &input, &proof), None);
+        assert!(read_to_string(&proof).unwrap().ends_with("0\n"));
+
+        // Runs without a proof don't share the cached output.
+        assert!(!Path::new(&format!("{}.output", input)).exists());
+
+        for filename in [solver, input.clone(), proof, format!("{}.proof.output", input)] {
+            remove_file(filename).unwrap();
+        }
+    }
+}
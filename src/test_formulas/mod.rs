@@ -0,0 +1,75 @@
+use crate::formula::Formula;
+use crate::clause::Clause;
+use crate::literal::Literal;
+use crate::model::Model;
+
+// Small formulas written as DIMACS numbers, shared by the tests of the solver,
+// the proof checker and the preprocessor.
+pub fn formula(number_of_variables: usize, clauses: &[Vec<isize>]) -> Formula {
+    let mut formula = Formula::new();
+
+    for _ in 0..number_of_variables {
+        formula.new_variable();
+    }
+
+    for numbers in clauses {
+        let mut clause = Clause::new();
+
+        for number in numbers {
+            clause.add(Literal::from_dimacs(*number));
+        }
+
+        formula.add_clause(clause);
+    }
+
+    formula
+}
+
+pub fn satisfies(model: &Model, clauses: &[Vec<isize>]) -> bool {
+    clauses.iter().all(|numbers| {
+        numbers.iter().any(|n| model.satisfies(&[Literal::from_dimacs(*n)]))
+    })
+}
+
+// Each of the pigeons must be in one of the holes but no two can share a hole.
+pub fn pigeonhole(pigeons: usize, holes: usize) -> Vec<Vec<isize>> {
+    let variable = |pigeon: usize, hole: usize| (pigeon * holes + hole + 1) as isize;
+    let mut clauses = vec![];
+
+    for pigeon in 0..pigeons {
+        clauses.push((0..holes).map(|hole| variable(pigeon, hole)).collect());
+    }
+
+    for hole in 0..holes {
+        for a in 0..pigeons {
+            for b in (a + 1)..pigeons {
+                clauses.push(vec![-variable(a, hole), -variable(b, hole)]);
+            }
+        }
+    }
+
+    clauses
+}
+
+// A linear congruential generator so random formulas are the same every run.
+pub struct Random {
+    seed: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    pub fn below(&mut self, limit: u64) -> u64 {
+        self.seed = self.seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.seed >> 33) % limit
+    }
+
+    pub fn clause(&mut self, width: u64, number_of_variables: u64) -> Vec<isize> {
+        (0..width).map(|_| {
+            let number = self.below(number_of_variables) as isize + 1;
+            if self.below(2) == 0 { number } else { -number }
+        }).collect()
+    }
+}